@@ -0,0 +1,23 @@
+use std::{env, fs, path::Path};
+
+/// Bundled data files, the placeholder used when one is missing, and the script that creates it.
+const BUNDLES: [(&str, &str, &str); 2] = [
+	("data/countries.json", "[]", "scripts/update-snapshot.sh"),
+	("data/world.geojson", r#"{"type":"FeatureCollection","features":[]}"#, "scripts/update-map.sh"),
+];
+
+fn main() {
+	let out_dir = env::var("OUT_DIR").unwrap();
+
+	for (path, placeholder, script) in BUNDLES {
+		println!("cargo:rerun-if-changed={path}");
+
+		let out = Path::new(&out_dir).join(Path::new(path).file_name().unwrap());
+		if Path::new(path).exists() {
+			fs::copy(path, out).unwrap();
+		} else {
+			println!("cargo:warning={path} not found, building without it. Run {script} to create it.");
+			fs::write(out, placeholder).unwrap();
+		}
+	}
+}
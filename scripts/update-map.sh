@@ -0,0 +1,35 @@
+#!/usr/bin/env sh
+# Refreshes the country shapes drawn on the world map, from Natural Earth's
+# 1:110m admin-0 boundaries. Features are reduced to their geometry and the
+# REST Countries `cca3` code they belong to. Run it by hand and commit
+# data/world.geojson. CI runs it only while the file is missing.
+set -eu
+
+# Pinned to a release so a refresh only changes the shapes when the version is bumped
+NATURAL_EARTH_VERSION="v5.1.2"
+SOURCE_URL="${WORLD_RANKS_MAP_URL:-https://raw.githubusercontent.com/nvkelso/natural-earth-vector/$NATURAL_EARTH_VERSION/geojson/ne_110m_admin_0_countries.geojson}"
+OUT="$(dirname "$0")/../data/world.geojson"
+TMP="$(mktemp)"
+trap 'rm -f "$TMP"' EXIT
+
+curl -fsSL "$SOURCE_URL" -o "$TMP"
+
+# Natural Earth uses -99 for some ISO codes and its own codes for a few
+# territories, so fall back to ADM0_A3 and rename those to match REST Countries.
+jq -c '
+	{ "KOS": "UNK", "SDS": "SSD", "SAH": "ESH", "PSX": "PSE" } as $renames
+	| {
+		type: "FeatureCollection",
+		features: [
+			.features[]
+			| (if .properties.ISO_A3 != "-99" then .properties.ISO_A3 else .properties.ADM0_A3 end) as $code
+			| {
+				type: "Feature",
+				properties: { cca3: ($renames[$code] // $code) },
+				geometry: .geometry
+			}
+		]
+	}
+' "$TMP" > "$OUT"
+
+echo "Wrote $(jq '.features | length' "$OUT") shapes to $OUT"
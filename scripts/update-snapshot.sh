@@ -0,0 +1,30 @@
+#!/usr/bin/env sh
+# Refreshes the country snapshot that is bundled into the app for offline use.
+# Run it by hand and commit data/countries.json. CI runs it only while the file is missing.
+# REST Countries only accepts up to 10 fields per request, so the records are
+# fetched in groups and merged on `cca3`.
+set -eu
+
+BASE_URL="${WORLD_RANKS_API_URL:-https://restcountries.com/v3.1}"
+# One group of fields per line, shared with the command-line tools in src/dataset.rs.
+# The first group lists the countries that are kept.
+FIELDS="$(dirname "$0")/snapshot-fields.txt"
+OUT="$(dirname "$0")/../data/countries.json"
+TMP="$(mktemp -d)"
+trap 'rm -rf "$TMP"' EXIT
+
+group=0
+while read -r fields; do
+	[ -n "$fields" ] || continue
+	curl -fsSL "$BASE_URL/all?fields=$fields" -o "$TMP/$(printf '%02d' "$group").json"
+	group=$((group + 1))
+done < "$FIELDS"
+
+jq -s '
+	(.[1:] | add | group_by(.cca3) | map({ (.[0].cca3): add }) | add) as $extra
+	| .[0]
+	| map(. + $extra[.cca3])
+	| sort_by(.cca3)
+' "$TMP"/*.json > "$OUT"
+
+echo "Wrote $(jq length "$OUT") countries to $OUT"
@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::types::{
+	Country,
+	CountryOverview,
+	NeighbouringCountry,
+	CCA3
+};
+
+/// How long a response is served from the cache before it is fetched again.
+const TTL_MS: f64 = 6.0 * 60.0 * 60.0 * 1000.0;
+#[cfg(feature = "web")]
+const STORAGE_KEY: &str = "world-ranks-cache";
+/// Bump whenever a cached type gains, loses or changes a field, so caches written by an older
+/// build are dropped instead of loading with those fields defaulted.
+#[cfg(feature = "web")]
+const SCHEMA_VERSION: u32 = 3;
+
+/// Responses shared between every page, restored from `localStorage` on web.
+pub static CACHE: GlobalSignal<ResponseCache> = Signal::global(ResponseCache::restore);
+
+#[derive(Serialize, Deserialize, Clone)]
+struct Entry<T> {
+	value: T,
+	fetched_at: f64,
+}
+
+impl<T> Entry<T> {
+	fn new(value: T) -> Self {
+		Entry {
+			value,
+			fetched_at: now_ms()
+		}
+	}
+
+	fn is_fresh(&self) -> bool {
+		now_ms() - self.fetched_at < TTL_MS
+	}
+}
+
+/// The cache as written to `localStorage`, tagged with the [`SCHEMA_VERSION`] it was written with.
+#[cfg(feature = "web")]
+#[derive(Serialize, Deserialize)]
+struct Stored<C> {
+	version: u32,
+	cache: C,
+}
+
+/// Cached responses keyed by endpoint: the `/all` overview list, `/alpha/{cca3}` records and
+/// the neighbours fetched with `/alpha?codes=`.
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct ResponseCache {
+	overviews: Option<Entry<Vec<CountryOverview>>>,
+	countries: HashMap<CCA3, Entry<Country>>,
+	neighbours: HashMap<CCA3, Entry<NeighbouringCountry>>,
+}
+
+impl ResponseCache {
+	pub fn overviews(&self) -> Option<&[CountryOverview]> {
+		self.overviews
+			.as_ref()
+			.filter(|entry| entry.is_fresh())
+			.map(|entry| entry.value.as_slice())
+	}
+
+	pub fn overview(&self, cca3: CCA3) -> Option<&CountryOverview> {
+		self.overviews()?.iter().find(|c| c.cca3 == cca3)
+	}
+
+	pub fn country(&self, cca3: CCA3) -> Option<&Country> {
+		self.countries
+			.get(&cca3)
+			.filter(|entry| entry.is_fresh())
+			.map(|entry| &entry.value)
+	}
+
+	/// Neighbours only need a name, flag and code, so any cached record will do.
+	/// Returns `None` unless every code can be answered from the cache.
+	pub fn neighbours(&self, codes: &[CCA3]) -> Option<Vec<NeighbouringCountry>> {
+		codes
+			.iter()
+			.map(|&cca3| {
+				if let Some(overview) = self.overview(cca3) {
+					Some(NeighbouringCountry {
+						name: overview.name.clone(),
+						flags: overview.flags.clone(),
+						cca3,
+					})
+				} else if let Some(country) = self.country(cca3) {
+					Some(NeighbouringCountry {
+						name: country.name.clone(),
+						flags: country.flags.clone(),
+						cca3,
+					})
+				} else {
+					self.neighbours
+						.get(&cca3)
+						.filter(|entry| entry.is_fresh())
+						.map(|entry| entry.value.clone())
+				}
+			})
+			.collect()
+	}
+
+	pub fn store_overviews(&mut self, overviews: Vec<CountryOverview>) {
+		self.overviews = Some(Entry::new(overviews));
+		self.persist();
+	}
+
+	pub fn store_countries(&mut self, countries: Vec<Country>) {
+		for country in countries {
+			self.countries.insert(country.cca3, Entry::new(country));
+		}
+		self.persist();
+	}
+
+	pub fn store_neighbours(&mut self, neighbours: Vec<NeighbouringCountry>) {
+		for neighbour in neighbours {
+			self.neighbours.insert(neighbour.cca3, Entry::new(neighbour));
+		}
+		self.persist();
+	}
+
+	fn restore() -> Self {
+		#[cfg(feature = "web")]
+		{
+			let mut cache = local_storage()
+				.and_then(|storage| storage.get_item(STORAGE_KEY).ok().flatten())
+				.and_then(|json| serde_json::from_str::<Stored<ResponseCache>>(&json).ok())
+				.filter(|stored| stored.version == SCHEMA_VERSION)
+				.map(|stored| stored.cache)
+				.unwrap_or_default();
+			cache.overviews = cache.overviews.filter(|entry| entry.is_fresh());
+			cache.countries.retain(|_, entry| entry.is_fresh());
+			cache.neighbours.retain(|_, entry| entry.is_fresh());
+			cache
+		}
+		#[cfg(not(feature = "web"))]
+		ResponseCache::default()
+	}
+
+	fn persist(&self) {
+		#[cfg(feature = "web")]
+		{
+			let stored = Stored {
+				version: SCHEMA_VERSION,
+				cache: self
+			};
+			if let (Some(storage), Ok(json)) = (local_storage(), serde_json::to_string(&stored)) {
+				// Storage can be full or disabled, in which case the cache just lives for this session.
+				let _ = storage.set_item(STORAGE_KEY, &json);
+			}
+		}
+	}
+}
+
+#[cfg(feature = "web")]
+fn local_storage() -> Option<web_sys::Storage> {
+	web_sys::window()?.local_storage().ok().flatten()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn now_ms() -> f64 {
+	js_sys::Date::now()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now_ms() -> f64 {
+	std::time::SystemTime::now()
+		.duration_since(std::time::UNIX_EPOCH)
+		.map(|d| d.as_secs_f64() * 1000.0)
+		.unwrap_or_default()
+}
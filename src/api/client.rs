@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+use dioxus::prelude::*;
+use gloo_net::http::Request;
+use serde::de::DeserializeOwned;
+use crate::{
+	api::{ApiError, Snapshot, CACHE},
+	types::{
+		Country,
+		CountryOverview,
+		NeighbouringCountry,
+		SearchFields,
+		CCA3
+	}
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://restcountries.com/v3.1";
+
+const OVERVIEW_FIELDS: &str = "flags,name,population,area,region,subregion,cca3,independent,unMember,borders";
+const SEARCH_FIELDS: &str = "cca3,cca2,altSpellings";
+const NEIGHBOUR_FIELDS: &str = "name,flags,cca3";
+
+/// The routes served at a client's base URL.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Backend {
+	/// REST Countries v3.1 or a mirror of it.
+	RestCountries,
+	/// `world-ranks-server`, serving `/api/countries` and `/api/countries/{cca3}`.
+	Proxy,
+	/// The bundled [`Snapshot`], so pages pre-rendered on the server match what the browser
+	/// shows before its first fetch.
+	Snapshot,
+}
+
+/// Typed access to the REST Countries v3.1 API, any mirror serving the same routes, or the
+/// `world-ranks-server` proxy. Responses are served from [`CACHE`] while they are fresh.
+#[derive(Clone, PartialEq)]
+pub struct CountriesClient {
+	base_url: String,
+	backend: Backend,
+}
+
+impl Default for CountriesClient {
+	/// Uses `WORLD_RANKS_PROXY_URL` or `WORLD_RANKS_API_URL` from the build environment when set,
+	/// otherwise restcountries.com. The server half of a fullstack build reads the snapshot.
+	fn default() -> Self {
+		if cfg!(feature = "server") {
+			return CountriesClient::with_backend("", Backend::Snapshot);
+		}
+		match option_env!("WORLD_RANKS_PROXY_URL") {
+			Some(proxy_url) => CountriesClient::proxy(proxy_url),
+			None => CountriesClient::new(option_env!("WORLD_RANKS_API_URL").unwrap_or(DEFAULT_BASE_URL))
+		}
+	}
+}
+
+impl CountriesClient {
+	pub fn new(base_url: impl Into<String>) -> Self {
+		CountriesClient::with_backend(base_url, Backend::RestCountries)
+	}
+
+	pub fn proxy(base_url: impl Into<String>) -> Self {
+		CountriesClient::with_backend(base_url, Backend::Proxy)
+	}
+
+	fn with_backend(base_url: impl Into<String>, backend: Backend) -> Self {
+		let base_url = base_url.into();
+		CountriesClient {
+			base_url: base_url.trim_end_matches('/').to_string(),
+			backend
+		}
+	}
+
+	pub async fn all_overviews(&self) -> Result<Vec<CountryOverview>, ApiError> {
+		if let Some(overviews) = CACHE.peek().overviews() {
+			return Ok(overviews.to_vec());
+		}
+
+		let overviews = match self.backend {
+			Backend::RestCountries => self.rest_overviews().await?,
+			// The proxy already includes the search fields
+			Backend::Proxy => self.get("/api/countries").await?,
+			Backend::Snapshot => Snapshot::overviews(),
+		};
+		CACHE.write().store_overviews(overviews.clone());
+		Ok(overviews)
+	}
+
+	async fn rest_overviews(&self) -> Result<Vec<CountryOverview>, ApiError> {
+		let mut overviews: Vec<CountryOverview> = self.get(&format!("/all?fields={OVERVIEW_FIELDS}")).await?;
+		let search_fields: HashMap<CCA3, SearchFields> = self.get::<Vec<SearchFields>>(&format!("/all?fields={SEARCH_FIELDS}"))
+			.await?
+			.into_iter()
+			.map(|fields| (fields.cca3, fields))
+			.collect();
+		for overview in overviews.iter_mut() {
+			if let Some(fields) = search_fields.get(&overview.cca3) {
+				overview.cca2 = fields.cca2.clone();
+				overview.alt_spellings = fields.alt_spellings.clone();
+			}
+		}
+		Ok(overviews)
+	}
+
+	pub async fn by_code(&self, cca3: CCA3) -> Result<Country, ApiError> {
+		if let Some(country) = CACHE.peek().country(cca3) {
+			return Ok(country.clone());
+		}
+
+		let country = match self.backend {
+			Backend::RestCountries => self.get::<Vec<Country>>(&format!("/alpha/{cca3}"))
+				.await?
+				.into_iter()
+				.next()
+				.ok_or(ApiError::NotFound)?,
+			Backend::Proxy => self.get(&format!("/api/countries/{cca3}")).await?,
+			Backend::Snapshot => Snapshot::country(cca3).ok_or(ApiError::NotFound)?,
+		};
+		CACHE.write().store_countries(vec![country.clone()]);
+		Ok(country)
+	}
+
+	/// Full records for several countries in the order requested. Codes the API does not
+	/// know are left out.
+	pub async fn countries_by_codes(&self, codes: &[CCA3]) -> Result<Vec<Country>, ApiError> {
+		let missing = codes
+			.iter()
+			.filter(|&&cca3| CACHE.peek().country(cca3).is_none())
+			.map(|code| code.as_str())
+			.collect::<Vec<_>>();
+
+		if !missing.is_empty() {
+			let joined = missing.join(",");
+			let fetched: Vec<Country> = match self.backend {
+				Backend::RestCountries => self.get(&format!("/alpha?codes={joined}")).await?,
+				Backend::Proxy => self.get(&format!("/api/countries?codes={joined}")).await?,
+				Backend::Snapshot => codes.iter().filter_map(|&cca3| Snapshot::country(cca3)).collect(),
+			};
+			CACHE.write().store_countries(fetched);
+		}
+
+		let cache = CACHE.peek();
+		Ok(
+			codes
+				.iter()
+				.filter_map(|&cca3| cache.country(cca3).cloned())
+				.collect()
+		)
+	}
+
+	pub async fn by_codes(&self, codes: &[CCA3]) -> Result<Vec<NeighbouringCountry>, ApiError> {
+		if let Some(neighbours) = CACHE.peek().neighbours(codes) {
+			return Ok(neighbours);
+		}
+
+		let joined = codes
+			.iter()
+			.map(|code| code.as_str())
+			.collect::<Vec<_>>()
+			.join(",");
+
+		let neighbours: Vec<NeighbouringCountry> = match self.backend {
+			Backend::RestCountries => self.get(&format!("/alpha?fields={NEIGHBOUR_FIELDS}&codes={joined}")).await?,
+			Backend::Proxy => self.get(&format!("/api/countries?codes={joined}")).await?,
+			Backend::Snapshot => Snapshot::neighbours(codes).ok_or(ApiError::NotFound)?,
+		};
+		CACHE.write().store_neighbours(neighbours.clone());
+		Ok(neighbours)
+	}
+
+	async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, ApiError> {
+		let response = Request::get(&format!("{}{path}", self.base_url))
+			.send()
+			.await
+			.map_err(|e| ApiError::Network(e.to_string()))?;
+
+		match response.status() {
+			404 => Err(ApiError::NotFound),
+			_ if !response.ok() => Err(ApiError::Status(response.status())),
+			_ => response
+				.json::<T>()
+				.await
+				.map_err(|e| ApiError::Decode(e.to_string()))
+		}
+	}
+}
@@ -0,0 +1,26 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiError {
+	/// The request never produced a response, e.g. the host is unreachable.
+	Network(String),
+	/// The API answered with 404 for the requested resource.
+	NotFound,
+	/// The API answered with any other non-success status code.
+	Status(u16),
+	/// The response body did not match the expected schema.
+	Decode(String),
+}
+
+impl fmt::Display for ApiError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ApiError::Network(reason) => write!(f, "Could not reach the countries API: {reason}"),
+			ApiError::NotFound => write!(f, "The requested country could not be found"),
+			ApiError::Status(status) => write!(f, "The countries API responded with status {status}"),
+			ApiError::Decode(reason) => write!(f, "Unexpected response from the countries API: {reason}"),
+		}
+	}
+}
+
+impl std::error::Error for ApiError {}
@@ -0,0 +1,9 @@
+mod cache;
+mod client;
+mod error;
+mod snapshot;
+
+pub use cache::*;
+pub use client::*;
+pub use error::*;
+pub use snapshot::*;
@@ -0,0 +1,44 @@
+use std::sync::LazyLock;
+use serde::Deserialize;
+use serde_json::Value;
+use crate::types::{
+	Country,
+	CountryOverview,
+	NeighbouringCountry,
+	CCA3
+};
+
+/// Country records bundled at build time from `data/countries.json`.
+static RECORDS: LazyLock<Vec<Value>> = LazyLock::new(|| {
+	serde_json::from_slice(include_bytes!(concat!(env!("OUT_DIR"), "/countries.json"))).unwrap_or_default()
+});
+
+/// Offline copy of the REST Countries data, used until the network responds or when it is unreachable.
+pub struct Snapshot;
+
+impl Snapshot {
+	pub fn overviews() -> Vec<CountryOverview> {
+		RECORDS
+			.iter()
+			.filter_map(|record| CountryOverview::deserialize(record).ok())
+			.collect()
+	}
+
+	pub fn country(cca3: CCA3) -> Option<Country> {
+		Country::deserialize(Self::record(cca3)?).ok()
+	}
+
+	/// Returns `None` unless every code is present in the snapshot, so callers never show a partial list.
+	pub fn neighbours(codes: &[CCA3]) -> Option<Vec<NeighbouringCountry>> {
+		codes
+			.iter()
+			.map(|&cca3| NeighbouringCountry::deserialize(Self::record(cca3)?).ok())
+			.collect()
+	}
+
+	fn record(cca3: CCA3) -> Option<&'static Value> {
+		RECORDS
+			.iter()
+			.find(|record| record.get("cca3").and_then(Value::as_str) == Some(cca3.as_str()))
+	}
+}
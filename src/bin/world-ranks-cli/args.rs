@@ -0,0 +1,212 @@
+use std::str::FromStr;
+use world_ranks::{
+	dataset::Source,
+	export::ExportFormat,
+	search::ParsedQuery,
+	types::{ListQuery, Region, SortDirection, SortKey, Status, StatusFilter, ValueRange, CCA3},
+};
+use crate::output::Output;
+
+pub const USAGE: &str = "\
+Usage: world-ranks-cli [--dataset <file> | --upstream <url>] <command> [options]
+
+Commands:
+  list                 Countries matching the filters, as a table by default
+  show <CCA3>          Everything known about one country
+  neighbours <CCA3>    Countries sharing a land border with one country
+  export               Countries matching the filters in the app's export columns
+
+Filters for list and export:
+  --search <text>              Name, code or alternative spelling, and filters such as
+                               \"region:europe pop>10m area<50k un:yes sort:-density\"
+  --region <region>            Repeat for several regions
+  --subregion <subregion>      Repeat for several subregions
+  --un <yes|no>                Member of the United Nations
+  --independent <yes|no>
+  --population <min-max>       Either bound may be left out, e.g. 10000000-
+  --area <min-max>             In km², e.g. -50000
+  --sort <key>                 Name, population, area, density, region, subregion or neighbours; repeat to break ties,
+                               prefix with - to sort descending, e.g. --sort -population --sort name
+  --desc                       Sort the key before it descending, or the first key when it comes first
+  --limit <n>
+
+Output:
+  --output <table|json|csv>    For list, show and neighbours
+  --format <csv|json|xlsx>     For export";
+
+pub enum Command {
+	List,
+	Show(CCA3),
+	Neighbours(CCA3),
+	Export,
+}
+
+pub struct Args {
+	pub source: Source,
+	pub command: Command,
+	/// Filters and sort for `list` and `export`, as in the country list URL.
+	pub query: ListQuery,
+	pub limit: Option<usize>,
+	pub output: Output,
+	pub format: ExportFormat,
+}
+
+impl Args {
+	pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+		let mut source = Source::Upstream(
+			std::env::var("WORLD_RANKS_API_URL").unwrap_or_else(|_| "https://restcountries.com/v3.1".to_string())
+		);
+		let mut positional = Vec::new();
+		let mut query = ListQuery {
+			sort: Vec::new(),
+			..ListQuery::default()
+		};
+		// `--desc` before any `--sort` applies to whichever key ends up first
+		let mut first_descending = false;
+		let mut limit = None;
+		let mut output = Output::Table;
+		let mut format = ExportFormat::Csv;
+
+		let mut args = args.into_iter();
+		while let Some(arg) = args.next() {
+			if !arg.starts_with("--") {
+				positional.push(arg);
+				continue;
+			}
+			if arg == "--desc" {
+				match query.sort.last_mut() {
+					Some(key) => key.direction = SortDirection::Descending,
+					None => first_descending = true,
+				}
+				continue;
+			}
+
+			let value = args.next().ok_or_else(|| format!("{arg} needs a value"))?;
+			let invalid = || format!("Invalid value for {arg}: {value}");
+			match arg.as_str() {
+				"--dataset" => source = Source::File(value.into()),
+				"--upstream" => source = Source::Upstream(value),
+				"--search" => {
+					if let Some(error) = ParsedQuery::parse(&value).errors.first() {
+						return Err(format!("Invalid value for --search: {error}"));
+					}
+					query.search = value
+				},
+				"--region" => query.regions.push(Region::from_str(&value).map_err(|_| invalid())?),
+				"--subregion" => query.subregions.push(value),
+				"--un" => {
+					query.statuses.insert(Status::UN, status_filter(&value).ok_or_else(invalid)?);
+				},
+				"--independent" => {
+					query.statuses.insert(Status::Independent, status_filter(&value).ok_or_else(invalid)?);
+				},
+				"--population" => query.population = ValueRange::from_str(&value).map_err(|_| invalid())?,
+				"--area" => query.area = ValueRange::from_str(&value).map_err(|_| invalid())?,
+				"--sort" => query.sort.push(SortKey::from_str(&value).map_err(|_| invalid())?),
+				"--limit" => limit = Some(value.parse().map_err(|_| invalid())?),
+				"--output" => output = Output::from_str(&value).map_err(|_| invalid())?,
+				"--format" => format = ExportFormat::from_str(&value).map_err(|_| invalid())?,
+				_ => return Err(format!("Unknown option {arg}")),
+			}
+		}
+
+		if query.sort.is_empty() {
+			query.sort.push(SortKey::default());
+		}
+		if first_descending {
+			query.sort[0].direction = SortDirection::Descending;
+		}
+
+		let code = |value: Option<&String>| {
+			let value = value.ok_or("Missing the country code")?;
+			CCA3::from_str(&value.to_ascii_uppercase()).map_err(|e| e.to_string())
+		};
+		let command = match positional.first().map(String::as_str) {
+			Some("list") => Command::List,
+			Some("show") => Command::Show(code(positional.get(1))?),
+			Some("neighbours" | "neighbors") => Command::Neighbours(code(positional.get(1))?),
+			Some("export") => Command::Export,
+			Some(command) => return Err(format!("Unknown command {command}")),
+			None => return Err("Missing a command".to_string()),
+		};
+
+		Ok(Args {
+			source,
+			command,
+			query,
+			limit,
+			output,
+			format
+		})
+	}
+}
+
+fn status_filter(value: &str) -> Option<StatusFilter> {
+	match value.to_ascii_lowercase().as_str() {
+		"yes" | "true" | "1" => Some(StatusFilter::Yes),
+		"no" | "false" | "0" => Some(StatusFilter::No),
+		"any" => Some(StatusFilter::Any),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use world_ranks::types::SortBy;
+	use super::*;
+
+	fn parse(line: &str) -> Result<Args, String> {
+		Args::parse(line.split_whitespace().map(str::to_string))
+	}
+
+	fn sort(line: &str) -> Vec<String> {
+		parse(line).unwrap().query.sort.iter().map(SortKey::to_string).collect()
+	}
+
+	#[test]
+	fn reads_commands_filters_and_output() {
+		let args = parse("list --dataset countries.json --region europe --region Asia --un yes --population 1000000- --limit 5 --output csv").unwrap();
+
+		assert!(matches!(args.command, Command::List));
+		assert!(matches!(args.source, Source::File(ref path) if path.to_str() == Some("countries.json")));
+		assert_eq!(args.query.regions, [Region::Europe, Region::Asia]);
+		assert_eq!(args.query.statuses.get(&Status::UN), Some(&StatusFilter::Yes));
+		assert_eq!(args.query.population, ValueRange { min: Some(1_000_000.0), max: None });
+		assert_eq!(args.limit, Some(5));
+		assert_eq!(args.output, Output::Csv);
+
+		assert!(matches!(parse("show deu").unwrap().command, Command::Show(code) if code.as_str() == "DEU"));
+		assert!(matches!(parse("neighbors FRA").unwrap().command, Command::Neighbours(_)));
+	}
+
+	#[test]
+	fn sorts_by_the_default_key_unless_told_otherwise() {
+		assert_eq!(parse("list").unwrap().query.sort, [SortKey::default()]);
+		assert_eq!(sort("list --sort area --sort name"), ["Area", "Name"]);
+	}
+
+	#[test]
+	fn sorts_descending_per_key() {
+		assert_eq!(sort("list --sort -population --sort name"), ["-Population", "Name"]);
+		assert_eq!(sort("list --sort region --sort population --desc"), ["Region", "-Population"]);
+		assert_eq!(sort("list --desc --sort area --sort name"), ["-Area", "Name"]);
+
+		let args = parse("list --desc").unwrap();
+		assert_eq!(args.query.sort, [SortKey {
+			by: SortBy::default(),
+			direction: SortDirection::Descending
+		}]);
+	}
+
+	#[test]
+	fn rejects_bad_arguments() {
+		assert_eq!(parse("").err().unwrap(), "Missing a command");
+		assert_eq!(parse("rank").err().unwrap(), "Unknown command rank");
+		assert_eq!(parse("show").err().unwrap(), "Missing the country code");
+		assert_eq!(parse("list --limit").err().unwrap(), "--limit needs a value");
+		assert_eq!(parse("list --region Atlantis").err().unwrap(), "Invalid value for --region: Atlantis");
+		assert_eq!(parse("list --sort size").err().unwrap(), "Invalid value for --sort: size");
+		assert_eq!(parse("list --colour red").err().unwrap(), "Unknown option --colour");
+		assert!(parse("list --search un:maybe").err().unwrap().starts_with("Invalid value for --search: un:maybe"));
+	}
+}
@@ -0,0 +1,78 @@
+//! Queries the country data from the terminal.
+//!
+//! ```text
+//! world-ranks-cli list --region Europe --sort population --desc --limit 10
+//! world-ranks-cli show DEU
+//! world-ranks-cli neighbours DEU --output json
+//! world-ranks-cli export --format csv > countries.csv
+//! ```
+
+mod args;
+mod output;
+
+use std::{collections::HashMap, io::Write, process::exit};
+use serde::Deserialize;
+use serde_json::Value;
+use world_ranks::{
+	query::sort_data,
+	types::{Country, CountryOverview, CCA3},
+};
+use args::{Args, Command};
+
+fn main() {
+	let args = Args::parse(std::env::args().skip(1)).unwrap_or_else(|message| {
+		eprintln!("{message}\n\n{}", args::USAGE);
+		exit(2)
+	});
+
+	if let Err(message) = run(args) {
+		eprintln!("{message}");
+		exit(1)
+	}
+}
+
+fn run(args: Args) -> Result<(), String> {
+	let records = args.source.load()?;
+	let overviews = records
+		.iter()
+		.filter_map(|record| CountryOverview::deserialize(record).ok())
+		.collect::<Vec<_>>();
+
+	let bytes = match &args.command {
+		Command::List | Command::Export => {
+			let countries = args.query.apply(&overviews);
+			let countries = &countries[..args.limit.unwrap_or(countries.len()).min(countries.len())];
+
+			match args.command {
+				Command::Export => args.format.encode(countries).map_err(|e| e.to_string())?,
+				_ => args.output.countries(countries)?,
+			}
+		},
+		Command::Show(cca3) => args.output.country(&country(&records, *cca3)?)?,
+		Command::Neighbours(cca3) => {
+			let by_code = overviews
+				.iter()
+				.map(|c| (c.cca3, c.clone()))
+				.collect::<HashMap<_, _>>();
+			let neighbours = country(&records, *cca3)?
+				.borders
+				.unwrap_or_default()
+				.iter()
+				.filter_map(|cca3| by_code.get(cca3).cloned())
+				.collect::<Vec<_>>();
+			args.output.countries(&sort_data(neighbours, &args.query.sort))?
+		},
+	};
+
+	std::io::stdout()
+		.write_all(&bytes)
+		.map_err(|e| format!("Could not write the output: {e}"))
+}
+
+fn country(records: &[Value], cca3: CCA3) -> Result<Country, String> {
+	records
+		.iter()
+		.find(|record| world_ranks::dataset::cca3(record) == cca3.as_str())
+		.ok_or_else(|| format!("No country has the code {cca3}"))
+		.and_then(|record| Country::deserialize(record).map_err(|e| format!("Unexpected record for {cca3}: {e}")))
+}
@@ -0,0 +1,189 @@
+use strum_macros::EnumString;
+use thousands::Separable;
+use world_ranks::types::{Country, CountryOverview};
+
+#[derive(EnumString, PartialEq, Debug, Copy, Clone)]
+#[strum(ascii_case_insensitive)]
+pub enum Output {
+	Table,
+	Json,
+	Csv,
+}
+
+const COUNTRY_HEADERS: [&str; 6] = ["Code", "Name", "Population", "Area (km²)", "Region", "Subregion"];
+
+impl Output {
+	pub fn countries(self, countries: &[CountryOverview]) -> Result<Vec<u8>, String> {
+		if self == Output::Json {
+			return json(&countries);
+		}
+
+		let rows = countries
+			.iter()
+			.map(|c| {
+				let (population, area) = match self {
+					Output::Table => (c.population.separate_with_commas(), c.area.separate_with_commas()),
+					_ => (c.population.to_string(), c.area.to_string()),
+				};
+				vec![c.cca3.to_string(), c.name.common.clone(), population, area, c.region.to_string(), c.sub_region.clone()]
+			})
+			.collect::<Vec<_>>();
+
+		match self {
+			Output::Table => Ok(table(&COUNTRY_HEADERS, &rows, &[2, 3])),
+			_ => csv(&COUNTRY_HEADERS, &rows),
+		}
+	}
+
+	pub fn country(self, country: &Country) -> Result<Vec<u8>, String> {
+		if self == Output::Json {
+			return json(country);
+		}
+
+		let rows = facts(country)
+			.into_iter()
+			.map(|(field, value)| vec![field.to_string(), value])
+			.collect::<Vec<_>>();
+
+		match self {
+			Output::Table => Ok(table(&[], &rows, &[])),
+			_ => csv(&["Field", "Value"], &rows),
+		}
+	}
+}
+
+fn facts(country: &Country) -> Vec<(&'static str, String)> {
+	let list = |items: Vec<String>| items.join(", ");
+	let mut languages = country.languages.iter().flat_map(|l| l.values().cloned()).collect::<Vec<_>>();
+	languages.sort();
+	let mut currencies = country.currencies
+		.iter()
+		.flat_map(|cs| cs.iter().map(|(code, c)| format!("{} ({code})", c.name)))
+		.collect::<Vec<_>>();
+	currencies.sort();
+
+	vec![
+		("Name", country.name.common.clone()),
+		("Official name", country.name.official.clone()),
+		("Code", country.cca3.to_string()),
+		("Capital", list(country.capital.clone().unwrap_or_default())),
+		("Region", country.region.clone()),
+		("Subregion", country.sub_region.clone().unwrap_or_default()),
+		("Continents", list(country.continents.clone())),
+		("Population", country.population.separate_with_commas()),
+		("Area (km²)", country.area.separate_with_commas()),
+		("Density (/km²)", format!("{:.1}", country.density())),
+		("Languages", list(languages)),
+		("Currencies", list(currencies)),
+		("Borders", list(country.borders.iter().flatten().map(|b| b.to_string()).collect())),
+		("Calling codes", list(country.calling_codes())),
+		("Timezones", list(country.timezones.clone())),
+		("Top-level domains", list(country.tld.clone())),
+	]
+}
+
+fn json<T: serde::Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, String> {
+	let mut bytes = serde_json::to_vec_pretty(value).map_err(|e| e.to_string())?;
+	bytes.push(b'\n');
+	Ok(bytes)
+}
+
+fn csv(headers: &[&str], rows: &[Vec<String>]) -> Result<Vec<u8>, String> {
+	let mut writer = csv::Writer::from_writer(Vec::new());
+	writer.write_record(headers).map_err(|e| e.to_string())?;
+	for row in rows {
+		writer.write_record(row).map_err(|e| e.to_string())?;
+	}
+	writer.into_inner().map_err(|e| e.to_string())
+}
+
+/// Columns padded to their widest cell, with the `numeric` columns aligned right.
+fn table(headers: &[&str], rows: &[Vec<String>], numeric: &[usize]) -> Vec<u8> {
+	let headers = headers.iter().map(|h| h.to_string()).collect::<Vec<_>>();
+	let lines = Some(&headers).filter(|h| !h.is_empty()).into_iter().chain(rows).collect::<Vec<_>>();
+	let columns = lines.iter().map(|line| line.len()).max().unwrap_or_default();
+	let widths = (0..columns)
+		.map(|column| {
+			lines
+				.iter()
+				.filter_map(|line| line.get(column))
+				.map(|cell| cell.chars().count())
+				.max()
+				.unwrap_or_default()
+		})
+		.collect::<Vec<_>>();
+
+	let mut out = String::new();
+	for line in lines {
+		let cells = line
+			.iter()
+			.zip(&widths)
+			.enumerate()
+			.map(|(column, (cell, &width))| match numeric.contains(&column) {
+				true => format!("{cell:>width$}"),
+				false => format!("{cell:<width$}"),
+			})
+			.collect::<Vec<_>>();
+		out.push_str(cells.join("  ").trim_end());
+		out.push('\n');
+	}
+	out.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+	use serde::Deserialize;
+	use serde_json::Value;
+	use super::*;
+
+	fn records() -> Vec<Value> {
+		serde_json::from_str(include_str!("../../../tests/fixtures/countries.json")).unwrap()
+	}
+
+	fn overviews() -> Vec<CountryOverview> {
+		records().iter().map(|record| CountryOverview::deserialize(record).unwrap()).collect()
+	}
+
+	fn text(bytes: Result<Vec<u8>, String>) -> String {
+		String::from_utf8(bytes.unwrap()).unwrap()
+	}
+
+	#[test]
+	fn tables_pad_columns_and_align_numbers_right() {
+		assert_eq!(text(Output::Table.countries(&overviews()[..2])), "\
+Code  Name     Population  Area (km²)  Region  Subregion
+AUT   Austria   8,917,205      83,871  Europe  Central Europe
+DEU   Germany  83,240,525     357,114  Europe  Western Europe
+");
+	}
+
+	#[test]
+	fn csv_keeps_the_raw_numbers() {
+		let csv = text(Output::Csv.countries(&overviews()[1..2]));
+		let mut lines = csv.lines();
+
+		assert_eq!(lines.next(), Some("Code,Name,Population,Area (km²),Region,Subregion"));
+		assert!(lines.next().unwrap().starts_with("DEU,Germany,"));
+		assert_eq!(lines.next(), None);
+	}
+
+	#[test]
+	fn json_round_trips() {
+		let json = text(Output::Json.countries(&overviews()));
+		let parsed = serde_json::from_str::<Vec<CountryOverview>>(&json).unwrap();
+
+		assert!(json.ends_with('\n'));
+		assert_eq!(parsed.iter().map(|c| c.cca3).collect::<Vec<_>>(), overviews().iter().map(|c| c.cca3).collect::<Vec<_>>());
+	}
+
+	#[test]
+	fn lists_the_facts_of_one_country() {
+		let germany = Country::deserialize(&records()[1]).unwrap();
+
+		let table = text(Output::Table.country(&germany));
+		assert!(table.lines().any(|line| line.starts_with("Capital") && line.ends_with("Berlin")));
+
+		let csv = text(Output::Csv.country(&germany));
+		assert!(csv.starts_with("Field,Value\nName,Germany\n"));
+	}
+}
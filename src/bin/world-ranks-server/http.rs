@@ -0,0 +1,158 @@
+use std::{sync::Arc, time::Duration};
+use axum::{
+	extract::{Path, Query, State},
+	http::{
+		header::{CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_NONE_MATCH},
+		HeaderMap,
+		HeaderValue,
+		Method,
+		StatusCode
+	},
+	response::IntoResponse,
+	routing::get,
+	Router
+};
+use tower_http::{
+	cors::{Any, CorsLayer},
+	timeout::TimeoutLayer
+};
+use crate::proxy::{error, Endpoint, ListParams, Proxy, Response};
+
+/// Longest a request may take, loading the records included. A load that runs over keeps going
+/// in the background, so later requests are served from it.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Routes of the proxy. GET also answers HEAD, and CORS preflights are answered for browsers
+/// revalidating with `If-None-Match`.
+pub fn router(proxy: Arc<Proxy>) -> Router {
+	let cors = CorsLayer::new()
+		.allow_origin(Any)
+		.allow_methods([Method::GET, Method::HEAD])
+		.allow_headers([IF_NONE_MATCH])
+		.expose_headers([ETAG]);
+
+	Router::new()
+		.route("/api/countries", get(countries))
+		.route("/api/countries/{cca3}", get(country))
+		.fallback(|| async { error(404, "Unknown endpoint") })
+		.layer(TimeoutLayer::with_status_code(StatusCode::GATEWAY_TIMEOUT, REQUEST_TIMEOUT))
+		.layer(cors)
+		.with_state(proxy)
+}
+
+async fn countries(State(proxy): State<Arc<Proxy>>, Query(params): Query<ListParams>, headers: HeaderMap) -> Response {
+	respond(proxy, Endpoint::List(params), &headers).await
+}
+
+async fn country(State(proxy): State<Arc<Proxy>>, Path(cca3): Path<String>, headers: HeaderMap) -> Response {
+	respond(proxy, Endpoint::Country(cca3), &headers).await
+}
+
+/// Loading the records blocks on the file system or upstream, so it runs off the async workers.
+async fn respond(proxy: Arc<Proxy>, endpoint: Endpoint, headers: &HeaderMap) -> Response {
+	let if_none_match = headers
+		.get(IF_NONE_MATCH)
+		.and_then(|value| value.to_str().ok())
+		.map(str::to_string);
+
+	tokio::task::spawn_blocking(move || proxy.respond(&endpoint, if_none_match.as_deref()))
+		.await
+		.unwrap_or_else(|e| error(500, &e.to_string()))
+}
+
+impl IntoResponse for Response {
+	fn into_response(self) -> axum::response::Response {
+		let mut headers = HeaderMap::new();
+		headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+		// Browsers revalidate every time and get a 304 while nothing changed
+		headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+		if let Some(etag) = self.etag.and_then(|etag| HeaderValue::from_str(&etag).ok()) {
+			headers.insert(ETAG, etag);
+		}
+
+		let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+		(status, headers, self.body).into_response()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use axum::{body::Body, http::Request};
+	use tower::ServiceExt;
+	use world_ranks::dataset::Source;
+	use super::*;
+
+	fn fixture() -> Router {
+		router(Arc::new(Proxy::new(Source::File(
+			concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/countries.json").into()
+		))))
+	}
+
+	async fn send(router: &Router, request: Request<Body>) -> (StatusCode, HeaderMap, String) {
+		let response = router.clone().oneshot(request).await.unwrap();
+		let (parts, body) = response.into_parts();
+		let body = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+		(parts.status, parts.headers, String::from_utf8(body.to_vec()).unwrap())
+	}
+
+	fn get(uri: &str) -> Request<Body> {
+		Request::get(uri).body(Body::empty()).unwrap()
+	}
+
+	#[tokio::test]
+	async fn routes_the_list_and_single_countries() {
+		let router = fixture();
+
+		let (status, headers, body) = send(&router, get("/api/countries?region=Asia")).await;
+		assert_eq!(status, StatusCode::OK);
+		assert_eq!(headers[CONTENT_TYPE], "application/json");
+		assert!(body.contains("\"JPN\"") && !body.contains("\"DEU\""));
+
+		let (status, _, body) = send(&router, get("/api/countries?codes=FRA%2Cdeu")).await;
+		assert_eq!(status, StatusCode::OK);
+		assert!(body.contains("Berlin") && body.contains("Paris"));
+
+		let (status, _, body) = send(&router, get("/api/countries/jpn")).await;
+		assert_eq!(status, StatusCode::OK);
+		assert!(body.contains("Tokyo"));
+
+		let (status, _, body) = send(&router, get("/api/regions")).await;
+		assert_eq!(status, StatusCode::NOT_FOUND);
+		assert!(body.contains("Unknown endpoint"));
+	}
+
+	#[tokio::test]
+	async fn revalidates_with_etags() {
+		let router = fixture();
+		let (_, headers, _) = send(&router, get("/api/countries/FRA")).await;
+		let etag = headers[ETAG].to_str().unwrap();
+
+		let request = Request::get("/api/countries/FRA").header(IF_NONE_MATCH, etag).body(Body::empty()).unwrap();
+		let (status, headers, body) = send(&router, request).await;
+		assert_eq!(status, StatusCode::NOT_MODIFIED);
+		assert_eq!(headers[ETAG], etag);
+		assert!(body.is_empty());
+	}
+
+	#[tokio::test]
+	async fn answers_cors_preflights_and_rejects_other_methods() {
+		let router = fixture();
+
+		let preflight = Request::builder()
+			.method(Method::OPTIONS)
+			.uri("/api/countries")
+			.header("Origin", "http://localhost:8080")
+			.header("Access-Control-Request-Method", "GET")
+			.header("Access-Control-Request-Headers", "if-none-match")
+			.body(Body::empty())
+			.unwrap();
+		let (status, headers, _) = send(&router, preflight).await;
+		assert!(status.is_success());
+		assert_eq!(headers["access-control-allow-origin"], "*");
+		assert!(headers["access-control-allow-methods"].to_str().unwrap().contains("GET"));
+		assert!(headers["access-control-allow-headers"].to_str().unwrap().contains("if-none-match"));
+
+		let post = Request::post("/api/countries").body(Body::empty()).unwrap();
+		assert_eq!(send(&router, post).await.0, StatusCode::METHOD_NOT_ALLOWED);
+	}
+}
@@ -0,0 +1,49 @@
+//! Proxy for networks that block restcountries.com. Serves the country data the app needs from
+//! `/api/countries` and `/api/countries/{cca3}`, cached and with ETags.
+//!
+//! ```text
+//! world-ranks-server [--port 8080] [--dataset data/countries.json | --upstream https://restcountries.com/v3.1]
+//! ```
+
+mod http;
+mod proxy;
+
+use std::{process::exit, sync::Arc};
+use tokio::net::TcpListener;
+use world_ranks::dataset::Source;
+use proxy::Proxy;
+
+const USAGE: &str = "Usage: world-ranks-server [--port <port>] [--dataset <file> | --upstream <url>]";
+
+#[tokio::main]
+async fn main() {
+	let mut port = 8080_u16;
+	let mut source = Source::Upstream(
+		std::env::var("WORLD_RANKS_API_URL").unwrap_or_else(|_| "https://restcountries.com/v3.1".to_string())
+	);
+
+	let mut args = std::env::args().skip(1);
+	while let Some(arg) = args.next() {
+		match (arg.as_str(), args.next()) {
+			("--port", Some(value)) => port = value.parse().unwrap_or_else(|_| fail(&format!("Invalid port: {value}"))),
+			("--dataset", Some(path)) => source = Source::File(path.into()),
+			("--upstream", Some(url)) => source = Source::Upstream(url),
+			_ => fail(USAGE),
+		}
+	}
+
+	let listener = TcpListener::bind(("0.0.0.0", port))
+		.await
+		.unwrap_or_else(|e| fail(&format!("Could not listen on port {port}: {e}")));
+	println!("Serving countries from {source} on http://localhost:{port}/api/countries");
+
+	if let Err(error) = axum::serve(listener, http::router(Arc::new(Proxy::new(source)))).await {
+		eprintln!("Server stopped: {error}");
+		exit(1)
+	}
+}
+
+fn fail(message: &str) -> ! {
+	eprintln!("{message}");
+	exit(2)
+}
@@ -0,0 +1,347 @@
+use std::{
+	sync::{Arc, Mutex, PoisonError, RwLock, RwLockReadGuard, TryLockError},
+	time::{Duration, Instant}
+};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use world_ranks::dataset::{cca3, Source};
+
+/// How long records are served before they are loaded again, matching the app's own cache.
+const TTL: Duration = Duration::from_secs(6 * 60 * 60);
+/// How long a failed reload is remembered before upstream is tried again.
+const RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Fields of each country in the `/api/countries` list: the overview plus the search fields
+/// the app otherwise fetches separately.
+const OVERVIEW_FIELDS: [&str; 12] = [
+	"flags", "name", "population", "area", "region", "subregion", "cca3",
+	"independent", "unMember", "borders", "cca2", "altSpellings",
+];
+
+/// A request the router matched, with its path and query already decoded.
+pub enum Endpoint {
+	List(ListParams),
+	Country(String),
+}
+
+#[derive(Default, Deserialize)]
+pub struct ListParams {
+	pub region: Option<String>,
+	pub codes: Option<String>,
+}
+
+pub struct Response {
+	pub status: u16,
+	pub etag: Option<String>,
+	pub body: String,
+}
+
+#[derive(Default)]
+struct State {
+	records: Option<Arc<Vec<Value>>>,
+	loaded_at: Option<Instant>,
+	/// Why the last reload failed, and when to try again.
+	failure: Option<(String, Instant)>,
+}
+
+impl State {
+	fn fresh(&self) -> Option<Arc<Vec<Value>>> {
+		self.loaded_at.filter(|loaded_at| loaded_at.elapsed() < TTL)?;
+		self.records.clone()
+	}
+
+	fn backing_off(&self) -> Option<&str> {
+		self.failure
+			.as_ref()
+			.filter(|(_, retry_at)| Instant::now() < *retry_at)
+			.map(|(reason, _)| reason.as_str())
+	}
+}
+
+/// Serves `/api/countries` and `/api/countries/{cca3}` from records loaded once per [`TTL`].
+pub struct Proxy {
+	source: Source,
+	state: RwLock<State>,
+	/// Held by the one request reloading the records, so the others never wait on upstream
+	/// while there is anything to serve.
+	reloading: Mutex<()>,
+	/// How many times the records were loaded from the source.
+	#[cfg(test)]
+	loads: std::sync::atomic::AtomicUsize,
+}
+
+impl Proxy {
+	pub fn new(source: Source) -> Self {
+		Proxy {
+			source,
+			state: RwLock::new(State::default()),
+			reloading: Mutex::new(()),
+			#[cfg(test)]
+			loads: Default::default()
+		}
+	}
+
+	/// Answers a GET for `endpoint`. A failed reload keeps serving the records loaded before
+	/// it; only a proxy that never loaded any answers 502.
+	pub fn respond(&self, endpoint: &Endpoint, if_none_match: Option<&str>) -> Response {
+		let records = match self.records() {
+			Ok(records) => records,
+			Err(reason) => return error(502, &reason),
+		};
+
+		let body = match endpoint {
+			Endpoint::List(params) => list(&records, params),
+			Endpoint::Country(code) => match records.iter().find(|record| cca3(record).eq_ignore_ascii_case(code)) {
+				Some(record) => record.clone(),
+				None => return error(404, "Not Found"),
+			},
+		};
+		let body = body.to_string();
+		let etag = etag(&body);
+
+		if if_none_match.is_some_and(|header| matches_etag(header, &etag)) {
+			return Response {
+				status: 304,
+				etag: Some(etag),
+				body: String::new()
+			};
+		}
+
+		Response {
+			status: 200,
+			etag: Some(etag),
+			body
+		}
+	}
+
+	/// Fresh records, reloading them once they are older than [`TTL`]. The load runs outside
+	/// the state lock, and requests arriving meanwhile get the stale records, or wait for the
+	/// load when there are none yet. A failed load is not retried for [`RETRY_AFTER`].
+	fn records(&self) -> Result<Arc<Vec<Value>>, String> {
+		if let Some(records) = self.state().fresh() {
+			return Ok(records);
+		}
+
+		let _reloading = match self.reloading.try_lock() {
+			Ok(guard) => guard,
+			Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+			Err(TryLockError::WouldBlock) => {
+				// Read before blocking, so the reload can take the state lock to finish
+				let stale = self.state().records.clone();
+				match stale {
+					Some(stale) => return Ok(stale),
+					None => self.reloading.lock().unwrap_or_else(PoisonError::into_inner),
+				}
+			},
+		};
+
+		// Another request may have reloaded, or failed to, while this one waited
+		{
+			let state = self.state();
+			if let Some(records) = state.fresh() {
+				return Ok(records);
+			}
+			if let Some(reason) = state.backing_off() {
+				return state.records.clone().ok_or_else(|| reason.to_string());
+			}
+		}
+
+		#[cfg(test)]
+		self.loads.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+		let loaded = self.source.load();
+		let mut state = self.state.write().unwrap_or_else(PoisonError::into_inner);
+		match loaded {
+			Ok(records) => {
+				let records = Arc::new(records);
+				*state = State {
+					records: Some(records.clone()),
+					loaded_at: Some(Instant::now()),
+					failure: None
+				};
+				Ok(records)
+			},
+			Err(reason) => {
+				eprintln!("{reason}");
+				state.failure = Some((reason.clone(), Instant::now() + RETRY_AFTER));
+				state.records.clone().ok_or(reason)
+			}
+		}
+	}
+
+	fn state(&self) -> RwLockReadGuard<'_, State> {
+		self.state.read().unwrap_or_else(PoisonError::into_inner)
+	}
+}
+
+/// Responds in the same `{ status, message }` shape as REST Countries.
+pub fn error(status: u16, message: &str) -> Response {
+	Response {
+		status,
+		etag: None,
+		body: json!({ "status": status, "message": message }).to_string()
+	}
+}
+
+/// `?codes=DEU,FRA` gives full records in the order requested, leaving out unknown codes.
+/// Otherwise every overview, or those of `?region=Europe`.
+fn list(records: &[Value], params: &ListParams) -> Value {
+	if let Some(codes) = &params.codes {
+		return codes
+			.split(',')
+			.filter_map(|code| records.iter().find(|record| cca3(record).eq_ignore_ascii_case(code.trim())))
+			.cloned()
+			.collect();
+	}
+
+	let region = &params.region;
+	records
+		.iter()
+		.filter(|record| {
+			region.as_ref().is_none_or(|region| {
+				record.get("region").and_then(Value::as_str).is_some_and(|r| r.eq_ignore_ascii_case(region))
+			})
+		})
+		.map(|record| {
+			OVERVIEW_FIELDS
+				.iter()
+				.filter_map(|&field| Some((field.to_string(), record.get(field)?.clone())))
+				.collect::<Map<_, _>>()
+		})
+		.map(Value::Object)
+		.collect()
+}
+
+/// Strong ETag from a 64-bit FNV-1a hash of the body, stable across restarts.
+fn etag(body: &str) -> String {
+	let hash = body
+		.bytes()
+		.fold(0xcbf29ce484222325_u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3));
+	format!("\"{hash:016x}\"")
+}
+
+/// `If-None-Match` may list several tags, weak ones or `*`.
+fn matches_etag(header: &str, etag: &str) -> bool {
+	header
+		.split(',')
+		.map(str::trim)
+		.any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::{atomic::Ordering, Barrier};
+	use super::*;
+
+	fn fixture() -> Proxy {
+		Proxy::new(Source::File(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/countries.json").into()))
+	}
+
+	fn countries(region: Option<&str>, codes: Option<&str>) -> Endpoint {
+		Endpoint::List(ListParams {
+			region: region.map(str::to_string),
+			codes: codes.map(str::to_string)
+		})
+	}
+
+	fn country(code: &str) -> Endpoint {
+		Endpoint::Country(code.to_string())
+	}
+
+	fn json(response: &Response) -> Value {
+		serde_json::from_str(&response.body).unwrap()
+	}
+
+	#[test]
+	fn lists_overviews_with_search_fields() {
+		let response = fixture().respond(&countries(None, None), None);
+		let countries = json(&response);
+
+		assert_eq!(response.status, 200);
+		assert_eq!(countries.as_array().unwrap().len(), 4);
+		assert_eq!(countries[1]["cca3"], "DEU");
+		assert_eq!(countries[1]["altSpellings"][0], "DE");
+		assert!(countries[1].get("capital").is_none());
+	}
+
+	#[test]
+	fn filters_the_list_by_region_and_codes() {
+		let proxy = fixture();
+
+		let asia = json(&proxy.respond(&countries(Some("asia"), None), None));
+		assert_eq!(asia.as_array().unwrap().len(), 1);
+		assert_eq!(asia[0]["cca3"], "JPN");
+
+		let codes = json(&proxy.respond(&countries(None, Some("FRA,XXX,deu")), None));
+		assert_eq!(codes.as_array().unwrap().len(), 2);
+		assert_eq!(codes[0]["cca3"], "FRA");
+		assert_eq!(codes[1]["capital"][0], "Berlin");
+	}
+
+	#[test]
+	fn serves_a_single_country() {
+		let proxy = fixture();
+
+		let germany = proxy.respond(&country("deu"), None);
+		assert_eq!(germany.status, 200);
+		assert_eq!(json(&germany)["name"]["common"], "Germany");
+
+		assert_eq!(proxy.respond(&country("XXX"), None).status, 404);
+	}
+
+	#[test]
+	fn answers_not_modified_for_a_matching_etag() {
+		let proxy = fixture();
+		let first = proxy.respond(&country("FRA"), None);
+		let etag = first.etag.unwrap();
+
+		let cached = proxy.respond(&country("FRA"), Some(&format!("\"other\", W/{etag}")));
+		assert_eq!(cached.status, 304);
+		assert!(cached.body.is_empty());
+
+		let other = proxy.respond(&country("DEU"), Some(&etag));
+		assert_eq!(other.status, 200);
+		assert_ne!(other.etag.unwrap(), etag);
+	}
+
+	#[test]
+	fn reports_a_missing_dataset() {
+		let proxy = Proxy::new(Source::File("does-not-exist.json".into()));
+		assert_eq!(proxy.respond(&countries(None, None), None).status, 502);
+	}
+
+	#[test]
+	fn concurrent_requests_share_one_load() {
+		let proxy = Arc::new(fixture());
+		let start = Arc::new(Barrier::new(8));
+		let statuses = (0..8)
+			.map(|_| {
+				let (proxy, start) = (proxy.clone(), start.clone());
+				std::thread::spawn(move || {
+					start.wait();
+					proxy.respond(&country("JPN"), None).status
+				})
+			})
+			.collect::<Vec<_>>()
+			.into_iter()
+			.map(|thread| thread.join().unwrap())
+			.collect::<Vec<_>>();
+
+		assert_eq!(statuses, [200; 8]);
+		assert_eq!(proxy.loads.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn waits_before_retrying_a_failed_load() {
+		let path = std::env::temp_dir().join(format!("world-ranks-proxy-{}.json", std::process::id()));
+		let _ = std::fs::remove_file(&path);
+		let proxy = Proxy::new(Source::File(path.clone()));
+		assert_eq!(proxy.respond(&countries(None, None), None).status, 502);
+
+		// The dataset appearing right after the failure is only picked up once the retry is due
+		std::fs::copy(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/countries.json"), &path).unwrap();
+		let response = proxy.respond(&countries(None, None), None);
+		std::fs::remove_file(&path).unwrap();
+
+		assert_eq!(response.status, 502);
+	}
+}
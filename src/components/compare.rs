@@ -0,0 +1,268 @@
+use dioxus::prelude::*;
+use crate::{
+	api::{ApiError, CountriesClient, Snapshot},
+	components::{ErrorPanel, OfflineNotice},
+	types::{Country, CCA3List},
+	Route, TITLE
+};
+use thousands::Separable;
+
+#[component]
+pub fn Compare(codes: CCA3List) -> Element {
+	let client = use_context::<CountriesClient>();
+
+	*TITLE.write() = "Compare".to_string();
+
+	let mut countries_resource = use_resource(use_reactive!(|codes| {
+		let client = client.clone();
+		async move {
+			client.countries_by_codes(&codes.0).await
+		}
+	}));
+
+	let (countries, offline) = match countries_resource() {
+		Some(Ok(countries)) => (Some(countries), false),
+		Some(Err(error)) => {
+			let snapshot = codes.0
+				.iter()
+				.map(|&cca3| Snapshot::country(cca3))
+				.collect::<Option<Vec<_>>>()
+				.filter(|countries| !countries.is_empty());
+
+			match (snapshot, error) {
+				(Some(countries), _) => (Some(countries), true),
+				(None, ApiError::NotFound) => (Some(Vec::new()), false),
+				(None, error) => return rsx! {
+					main {
+						class: "flex justify-center items-center h-max mt-[-4rem]",
+						div {
+							class: "w-[95%] xl:w-5/6 2xl:w-3/4 bg-darker border border-dark rounded-2xl",
+							ErrorPanel {
+								error,
+								on_retry: move |_| countries_resource.restart()
+							}
+						}
+					}
+				}
+			}
+		},
+		None => (None, false)
+	};
+
+	rsx! {
+		main {
+			class: "flex justify-center items-center h-max mt-[-4rem]",
+			div {
+				class: "w-[95%] xl:w-5/6 2xl:w-3/4 flex flex-col justify-center items-center bg-darker border border-dark rounded-2xl gap-y-5 p-5 overflow-x-auto",
+				h2 {
+					class: "w-full text-lg font-semibold",
+					"Compare countries"
+				}
+				match countries {
+					None => rsx! {
+						div {
+							class: "w-full h-96 bg-dark rounded-md animate-pulse"
+						}
+					},
+					Some(countries) if countries.is_empty() => rsx! {
+						span {
+							"None of the codes \"{codes}\" match a country."
+						}
+					},
+					Some(countries) => rsx! {
+						table {
+							class: "w-full table-fixed",
+							thead {
+								tr {
+									class: "text-left border-b border-dark",
+									th {
+										class: "pb-2 w-40"
+									}
+									for country in countries.iter() {
+										th {
+											class: "pb-2 pr-4 align-bottom",
+											Link {
+												to: Route::CountryDetails { cca3: country.cca3 },
+												img {
+													class: "rounded-md max-h-12 mb-1",
+													src: "{country.flags.svg}",
+												}
+												span {
+													class: "block",
+													"{country.name.common}"
+												}
+											}
+											Link {
+												class: "text-xs text-light",
+												to: without(&codes, country),
+												"Remove"
+											}
+										}
+									}
+								}
+							}
+							tbody {
+								NumberRow {
+									label: "Population",
+									values: countries.iter().map(|c| c.population as f64).collect::<Vec<_>>(),
+									decimals: 0
+								}
+								NumberRow {
+									label: "Area (km²)",
+									values: countries.iter().map(|c| c.area as f64).collect::<Vec<_>>(),
+									decimals: 0
+								}
+								NumberRow {
+									label: "Density (/km²)",
+									values: countries.iter().map(|c| c.density() as f64).collect::<Vec<_>>(),
+									decimals: 1
+								}
+								ListRow {
+									label: "Capital",
+									values: countries.iter().map(|c| c.capital.clone().unwrap_or_default()).collect::<Vec<_>>()
+								}
+								ListRow {
+									label: "Languages",
+									values: countries
+										.iter()
+										.map(|c| c.languages.iter().flat_map(|l| l.values().cloned()).collect())
+										.collect::<Vec<_>>()
+								}
+								ListRow {
+									label: "Currencies",
+									values: countries
+										.iter()
+										.map(|c| c.currencies.iter().flat_map(|cs| cs.values().map(|c| c.name.clone())).collect())
+										.collect::<Vec<_>>()
+								}
+								ListRow {
+									label: "Region",
+									values: countries.iter().map(|c| vec![c.region.clone()]).collect::<Vec<_>>()
+								}
+								ListRow {
+									label: "Subregion",
+									values: countries.iter().map(|c| c.sub_region.iter().cloned().collect()).collect::<Vec<_>>()
+								}
+								ListRow {
+									label: "Borders",
+									values: countries
+										.iter()
+										.map(|c| c.borders.iter().flatten().map(|b| b.to_string()).collect())
+										.collect::<Vec<_>>()
+								}
+							}
+						}
+					}
+				}
+				if offline {
+					OfflineNotice {
+						on_retry: move |_| countries_resource.restart()
+					}
+				}
+			}
+		}
+	}
+}
+
+/// The comparison without `country`, or the list once fewer than two would remain.
+fn without(codes: &CCA3List, country: &Country) -> Route {
+	let remaining = codes.0
+		.iter()
+		.copied()
+		.filter(|cca3| *cca3 != country.cca3)
+		.collect::<Vec<_>>();
+
+	if remaining.len() < 2 {
+		Route::CountryList {
+			segments: Vec::new(),
+			query: Box::default()
+		}
+	} else {
+		Route::Compare { codes: CCA3List(remaining) }
+	}
+}
+
+/// Highlights the largest and smallest value when the countries differ.
+#[component]
+fn NumberRow(label: String, values: Vec<f64>, decimals: usize) -> Element {
+	let finite = values.iter().copied().filter(|v| v.is_finite());
+	let max = finite.clone().fold(f64::NEG_INFINITY, f64::max);
+	let min = finite.fold(f64::INFINITY, f64::min);
+	let differs = max > min;
+
+	rsx! {
+		tr {
+			class: "border-b border-dark",
+			td {
+				class: "py-3 text-light",
+				"{label}"
+			}
+			for value in values {
+				td {
+					class: match differs {
+						true if value == max => "py-3 pr-4 text-interact font-semibold",
+						true if value == min => "py-3 pr-4 text-light",
+						_ => "py-3 pr-4",
+					},
+					"{format_number(value, decimals)}"
+				}
+			}
+		}
+	}
+}
+
+fn format_number(value: f64, decimals: usize) -> String {
+	if !value.is_finite() {
+		return "No data".to_string();
+	}
+
+	let rounded = format!("{value:.decimals$}");
+	match rounded.split_once('.') {
+		Some((whole, fraction)) => format!("{}.{fraction}", whole.parse::<u64>().unwrap_or_default().separate_with_commas()),
+		None => rounded.parse::<u64>().unwrap_or_default().separate_with_commas(),
+	}
+}
+
+/// Highlights the entries that not every country shares.
+#[component]
+fn ListRow(label: String, values: Vec<Vec<String>>) -> Element {
+	let shared = |item: &String| values.iter().all(|items| items.contains(item));
+	let cells = values
+		.iter()
+		.map(|items| {
+			let mut items = items
+				.iter()
+				.map(|item| (item.clone(), shared(item)))
+				.collect::<Vec<_>>();
+			items.sort();
+			items
+		})
+		.collect::<Vec<_>>();
+
+	rsx! {
+		tr {
+			class: "border-b border-dark",
+			td {
+				class: "py-3 text-light align-top",
+				"{label}"
+			}
+			for items in cells {
+				td {
+					class: "py-3 pr-4 align-top",
+					if items.is_empty() {
+						span {
+							class: "text-light",
+							"No data"
+						}
+					}
+					for (item, is_shared) in items {
+						span {
+							class: if is_shared { "block" } else { "block text-interact" },
+							"{item}"
+						}
+					}
+				}
+			}
+		}
+	}
+}
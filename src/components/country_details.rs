@@ -1,75 +1,105 @@
 use dioxus::prelude::*;
-use gloo_net::http::Request;
-use crate::{types::{
-	Country,
-	NeighbouringCountry,
-	CCA3
-}, TITLE};
+use crate::{
+	api::{ApiError, CountriesClient, Snapshot, CACHE},
+	components::{CountryNotFound, ErrorPanel, OfflineNotice},
+	types::{Country, RouteQuery, CCA3},
+	TITLE
+};
 use thousands::Separable;
 use crate::Route;
 
 #[component]
 pub fn CountryDetails(cca3: CCA3) -> Element {
-	let country_resource = use_resource(
-		use_reactive!(|cca3| async move {
-			let vec = Request::get(
-				&format!(
-					"https://restcountries.com/v3.1/alpha/{cca3}"
-				))
-				.send()
-				.await
-				.unwrap()
-				.json::<Vec<Country>>()
-				.await
-				.unwrap();
-	
-			let country = vec.first().unwrap().clone();
-			*TITLE.write() = country.name.common.to_string();
-			
-			country
+	let client = use_context::<CountriesClient>();
+
+	let mut country_resource = use_resource({
+		let client = client.clone();
+		use_reactive!(|cca3| {
+			let client = client.clone();
+			async move {
+				client.by_code(cca3).await
+			}
 		})
-	);
+	});
 
-	let neighbour_resource = use_resource(move || {
+	let snapshot_country = use_memo(use_reactive!(|cca3| Snapshot::country(cca3)));
+	let cached_overview = use_memo(use_reactive!(|cca3| CACHE.peek().overview(cca3).cloned()));
+
+	let mut neighbour_resource = use_resource(move || {
+		let client = client.clone();
 		let country = country_resource.read().clone();
 		async move {
-			if let Some(country) = country {
-				match country.borders.as_ref() {
-					Some(borders) if !borders.is_empty() => {
-						let codes = borders
-							.iter()
-							.map(|code| code.as_str())
-							.collect::<Vec<_>>()
-							.join(",");
-
-						let vec = Request::get(
-							&format!(
-								"https://restcountries.com/v3.1/alpha?fields=name,flags,cca3&codes={codes}"
-							),
-						)
-						.send()
-						.await
-						.unwrap()
-						.json::<Vec<NeighbouringCountry>>()
-						.await
-						.unwrap();
+			match country {
+				Some(Ok(country)) => Some(
+					client.by_codes(country.borders.as_deref().unwrap_or_default()).await
+				),
+				_ => None
+			}
+		}
+	});
 
-						Some(vec)
+	// `partial` marks a country built from its list overview, which lacks capitals, languages,
+	// currencies and borders until the full record arrives.
+	let (country, offline, partial) = match country_resource() {
+		Some(Err(ApiError::NotFound)) => {
+			*TITLE.write() = "Not found".to_string();
+			return rsx! {
+				main {
+					class: "flex justify-center items-center h-max mt-[-4rem]",
+					div {
+						class: "w-[95%] xl:w-1/2 bg-darker border border-dark rounded-2xl",
+						CountryNotFound { cca3 }
 					}
-					_ => Some(Vec::<NeighbouringCountry>::new()),
 				}
-			} else {
-					None
 			}
-    }
-	});
+		},
+		Some(Err(error)) => match snapshot_country() {
+			Some(country) => (Some(country), true, false),
+			None => {
+				*TITLE.write() = "Error".to_string();
+				return rsx! {
+					main {
+						class: "flex justify-center items-center h-max mt-[-4rem]",
+						div {
+							class: "w-[95%] xl:w-1/2 bg-darker border border-dark rounded-2xl",
+							ErrorPanel {
+								error,
+								on_retry: move |_| country_resource.restart()
+							}
+						}
+					}
+				}
+			}
+		},
+		Some(Ok(country)) => (Some(country), false, false),
+		None => match (snapshot_country(), cached_overview()) {
+			(Some(country), _) => (Some(country), false, false),
+			(None, Some(overview)) => (Some(overview.into()), false, true),
+			(None, None) => (None, false, false)
+		}
+	};
+	let full_country = country.clone().filter(|_| !partial);
+
+	if let Some(country) = country.as_ref() {
+		*TITLE.write() = country.name.common.to_string();
+	}
+
+	let snapshot_neighbours = full_country
+		.as_ref()
+		.and_then(|c| Snapshot::neighbours(c.borders.as_deref().unwrap_or_default()));
+
+	let neighbours = match neighbour_resource() {
+		Some(Some(Ok(neighbours))) => Some(Ok(neighbours)),
+		Some(Some(Err(error))) => Some(snapshot_neighbours.ok_or(error)),
+		_ => snapshot_neighbours.map(Ok)
+	};
 
 	rsx! {
 		main {
 			class: "flex justify-center items-center h-max mt-[-4rem]",
 			div {
 				class: "w-[95%] xl:w-1/2 flex flex-col justify-center items-center bg-darker border border-dark rounded-2xl gap-y-5 pb-5",
-				match country_resource() {
+				match country.clone() {
 					None => rsx! {
 						div {
 							class: "w-1/3 mt-[-5%] rounded-md bg-dark h-52 animate-pulse"
@@ -84,7 +114,7 @@ pub fn CountryDetails(cca3: CCA3) -> Element {
 				}
 				section {
 					class: "text-center w-1/3",
-					match country_resource() {
+					match country.clone() {
 						None =>	rsx! {
 							div {
 								class: "w-full h-9 bg-dark rounded-2xl animate-pulse mb-2"
@@ -109,7 +139,7 @@ pub fn CountryDetails(cca3: CCA3) -> Element {
 					class: "flex flex-row justify-evenly w-full px-5",
 					div {
 						class: "bg-dark p-3 rounded-2xl has-[.loading]:animate-pulse",
-						match country_resource() {
+						match country.clone() {
 							None => rsx! {
 								span {
 									class: "block w-48 loading"
@@ -129,7 +159,7 @@ pub fn CountryDetails(cca3: CCA3) -> Element {
 					}
 					div {
 						class: "bg-dark p-3 rounded-2xl has-[.loading]:animate-pulse",
-						match country_resource() {
+						match country.clone() {
 							None => rsx! {
 								span {
 									class: "block w-48 loading"
@@ -155,7 +185,7 @@ pub fn CountryDetails(cca3: CCA3) -> Element {
 						span {
 							"Capital"
 						}
-						match country_resource() {
+						match full_country.clone() {
 							None => rsx! {
 								span {
 									class: "bg-dark w-40 rounded-md animate-pulse"
@@ -186,7 +216,7 @@ pub fn CountryDetails(cca3: CCA3) -> Element {
 						span {
 							"Subregion"
 						}
-						match country_resource() {
+						match country.clone() {
 							None => rsx! {
 								span {
 									class: "bg-dark w-40 rounded-md animate-pulse"
@@ -213,7 +243,7 @@ pub fn CountryDetails(cca3: CCA3) -> Element {
 						span {
 							"Language"
 						}
-						match country_resource() {
+						match full_country.clone() {
 							None => rsx! {
 								span {
 									class: "bg-dark w-40 rounded-md animate-pulse"
@@ -247,7 +277,7 @@ pub fn CountryDetails(cca3: CCA3) -> Element {
 						span {
 							"Currencies"
 						}
-						match country_resource() {
+						match full_country.clone() {
 							None => rsx! {
 								span {
 									class: "bg-dark w-40 rounded-md animate-pulse"
@@ -281,29 +311,47 @@ pub fn CountryDetails(cca3: CCA3) -> Element {
 						span {
 							"Continents"
 						}
-						match country_resource() {
+						match country.clone() {
 							None => rsx! {
 								span {
 									class: "bg-dark w-40 rounded-md animate-pulse"
 								}
 							},
-							Some(country) => rsx! {
+							Some(country) if country.continents.is_empty() => rsx! {
 								span {
 									"{country.region}"
 								}
+							},
+							Some(country) => rsx! {
+								span {
+									"{country.continents.join(\", \")}"
+								}
 							}
 						}
 					}
 				}
+				if let Some(country) = full_country.clone() {
+					MoreFacts { country }
+				}
 				section {
 					class: "w-full px-5 flex flex-col gap-5",
-					span {
-						"Neighbouring countries"
+					div {
+						class: "flex flex-row justify-between",
+						span {
+							"Neighbouring countries"
+						}
+						Link {
+							class: "text-sm text-interact",
+							to: Route::LandRoutes {
+								query: RouteQuery { from: Some(cca3), to: None }
+							},
+							"Find a land route"
+						}
 					}
 					div {
 						class: "flex flex-row flex-wrap gap-5 justify-center",
-						match neighbour_resource() {
-							Some(Some(neighbours)) if !neighbours.is_empty() => rsx! {
+						match neighbours {
+							Some(Ok(neighbours)) if !neighbours.is_empty() => rsx! {
 								for neighbour in neighbours.iter() {
 									div {
 										class: "1/5 md:w-1/6",
@@ -321,11 +369,17 @@ pub fn CountryDetails(cca3: CCA3) -> Element {
 									}
 								}
 							},
-							Some(Some(neighbours)) if neighbours.is_empty() => rsx! {
+							Some(Ok(_)) => rsx! {
 								span {
 									"No neighbours"
 								}
 							},
+							Some(Err(error)) => rsx! {
+								ErrorPanel {
+									error,
+									on_retry: move |_| neighbour_resource.restart()
+								}
+							},
 							_ => rsx! {
 								for _ in 0..5 {
 									div {
@@ -342,6 +396,129 @@ pub fn CountryDetails(cca3: CCA3) -> Element {
 						}
 					}
 				}
+				if offline {
+					section {
+						class: "w-full px-5",
+						OfflineNotice {
+							on_retry: move |_| country_resource.restart()
+						}
+					}
+				}
+			}
+		}
+	}
+}
+
+/// Collapsible list of the less prominent fields of a full country record.
+#[component]
+fn MoreFacts(country: Country) -> Element {
+	let codes = [Some(&country.cca2).filter(|c| !c.is_empty()), country.ccn3.as_ref(), country.cioc.as_ref()]
+		.into_iter()
+		.flatten()
+		.cloned()
+		.collect::<Vec<_>>();
+	let coordinates = match country.latlng.as_slice() {
+		[lat, lng, ..] => Some(format!(
+			"{:.2}° {}, {:.2}° {}",
+			lat.abs(),
+			if *lat >= 0.0 { "N" } else { "S" },
+			lng.abs(),
+			if *lng >= 0.0 { "E" } else { "W" }
+		)),
+		_ => None
+	};
+	let driving = country.car.side.as_ref().map(|side| match country.car.signs.as_slice() {
+		[] => format!("On the {side}"),
+		signs => format!("On the {side} ({})", signs.join(", "))
+	});
+	let demonym = country.demonym().map(|d| match d.female == d.male {
+		true => d.male.clone(),
+		false => format!("{} / {}", d.male, d.female)
+	});
+	let gini = country.latest_gini().map(|(year, gini)| format!("{gini} ({year})"));
+	let postal_code = country.postal_code.as_ref().map(|p| p.format.clone());
+	let landlocked = country.landlocked.map(|l| if l { "Yes" } else { "No" }.to_string());
+	let coat_of_arms = country.coat_of_arms.svg.clone().or(country.coat_of_arms.png.clone());
+
+	let facts = [
+		("Codes", Some(codes.join(" / ")).filter(|c| !c.is_empty())),
+		("Top-level domain", Some(country.tld.join(", ")).filter(|t| !t.is_empty())),
+		("Calling code", Some(country.calling_codes().join(", ")).filter(|c| !c.is_empty())),
+		("Timezones", Some(country.timezones.join(", ")).filter(|t| !t.is_empty())),
+		("Coordinates", coordinates),
+		("Landlocked", landlocked),
+		("Drives", driving),
+		("Demonym", demonym),
+		("Gini index", gini),
+		("FIFA code", country.fifa.clone()),
+		("Week starts on", country.start_of_week.clone()),
+		("Postal code format", postal_code),
+	].map(|(label, value)| (label, value.unwrap_or_else(|| "No data".to_string())));
+
+	rsx! {
+		details {
+			class: "w-full group",
+			summary {
+				class: "border-b border-dark p-5 cursor-pointer select-none",
+				"More facts"
+			}
+			for (label, value) in facts {
+				div {
+					class: "border-b border-dark w-full flex flex-row justify-between gap-5 p-5",
+					span {
+						"{label}"
+					}
+					span {
+						class: "text-right break-all",
+						"{value}"
+					}
+				}
+			}
+			div {
+				class: "border-b border-dark w-full flex flex-row justify-between items-center p-5",
+				span {
+					"Coat of arms"
+				}
+				match coat_of_arms {
+					Some(src) => rsx! {
+						img {
+							class: "h-16",
+							src: "{src}"
+						}
+					},
+					None => rsx! {
+						span {
+							"No data"
+						}
+					}
+				}
+			}
+			div {
+				class: "border-b border-dark w-full flex flex-row justify-between p-5",
+				span {
+					"Maps"
+				}
+				span {
+					class: "flex flex-row gap-3",
+					if let Some(url) = country.maps.open_street_maps.clone() {
+						a {
+							class: "text-interact",
+							href: "{url}",
+							target: "_blank",
+							rel: "noopener noreferrer",
+							"OpenStreetMap"
+						}
+					}
+					if let Some(url) = country.maps.google_maps.clone() {
+						a {
+							class: "text-interact",
+							href: "{url}",
+							target: "_blank",
+							rel: "noopener noreferrer",
+							"Google Maps"
+						}
+					}
+				}
 			}
 		}
 	}
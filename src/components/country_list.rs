@@ -1,140 +1,192 @@
 use std::str::FromStr;
 use std::collections::HashMap;
 use dioxus::prelude::*;
-use gloo_net::http::Request;
 use strum::IntoEnumIterator;
 use crate::{
+	components::{use_overviews, ErrorPanel, ExportPanel, OfflineNotice, Pagination, ScrollSentinel},
+	query::{toggle_region, toggle_subregion},
+	search::ParsedQuery,
 	types::{
 		CountryOverview,
-		FilterQuery,
+		CCA3,
+		CCA3List,
+		ListQuery,
+		PageSize,
+		Paginator,
 		Region,
 		SortBy,
-		Status
+		SortKey,
+		Status,
+		StatusFilter,
+		ValueRange
 	},
 	Route, TITLE
 };
 use thousands::Separable;
 
-const PAGE_SIZE: usize = 15;
-const CURRENT_PAGE: &str = "Current";
-const TOTAL_PAGES: &str = "Total";
-
-fn sort_data(mut countries: Vec<CountryOverview>, sort_by: &SortBy) -> Vec<CountryOverview> {
-	match sort_by {
-		SortBy::Area => countries.sort_by(|a, b| a.area.partial_cmp(&b.area).unwrap()),
-		SortBy::Name => countries.sort_by(|a, b| a.name.common.cmp(&b.name.common)),
-		SortBy::Population => countries.sort_by(|a, b| a.population.partial_cmp(&b.population).unwrap())
+fn sort_by_column(mut sort_signal: Signal<Vec<SortKey>>, by: SortBy, append: bool) {
+	let mut sort = sort_signal.write();
+	match sort.iter().position(|key| key.by == by) {
+		// Clicking the primary column, or shift-clicking any sorted column, flips its direction
+		Some(i) if append || i == 0 => sort[i].direction = sort[i].direction.toggle(),
+		_ if append => sort.push(SortKey::new(by)),
+		_ => *sort = vec![SortKey::new(by)],
 	}
-	countries
 }
 
-fn toggle_region(mut region_signal: Signal<Vec<Region>>, region: Region) {
-	if region_signal.read().contains(&region) {
-		region_signal.retain(|r| r != &region);
-	} else {
-		region_signal.push(region);
+fn set_sort_key(mut sort_signal: Signal<Vec<SortKey>>, index: usize, by: Option<SortBy>) {
+	let mut sort = sort_signal.write();
+	sort.truncate(index);
+	if let Some(by) = by {
+		sort.retain(|key| key.by != by);
+		sort.push(SortKey::new(by));
+	}
+	if sort.is_empty() {
+		sort.push(SortKey::default());
 	}
 }
 
-fn filter_data(countries: &[CountryOverview], queries: &[FilterQuery]) -> Vec<CountryOverview> {
-	countries
-		.iter()
-		.filter(|c| {
-			queries.iter().all(|query| match query {
-				FilterQuery::Text(text_query) => {
-					c.name.common.to_lowercase().contains(&text_query.to_lowercase()) ||
-					c.region.to_string().to_lowercase().contains(&text_query.to_lowercase()) ||
-					c.sub_region.to_string().to_lowercase().contains(&text_query.to_lowercase())
-				}
-				FilterQuery::Region(region_query) => region_query.is_empty() || region_query.contains(&c.region),
-				FilterQuery::Status(status_query) => {
-					let independent_query = status_query.get(&Status::Independent).unwrap();
-					let un_member_query = status_query.get(&Status::UN).unwrap();
-	
-					(!independent_query || c.independent) &&
-					( !un_member_query || c.un_member )
-				}
-			})
+/// Subregions present in the loaded data, grouped by region in display order.
+fn subregions_by_region(countries: &[CountryOverview]) -> Vec<(Region, Vec<String>)> {
+	Region::iter()
+		.map(|region| {
+			let mut subregions = countries
+				.iter()
+				.filter(|c| c.region == region && !c.sub_region.is_empty())
+				.map(|c| c.sub_region.clone())
+				.collect::<Vec<_>>();
+			subregions.sort();
+			subregions.dedup();
+			(region, subregions)
 		})
-		.cloned()
+		.filter(|(_, subregions)| !subregions.is_empty())
 		.collect()
 }
 
+/// Subregions typed into a URL may differ in case from the data, as when filtering.
+fn contains_subregion(subregions: &[String], subregion: &str) -> bool {
+	subregions.iter().any(|s| s.eq_ignore_ascii_case(subregion))
+}
+
+fn toggle_selected(mut selected_signal: Signal<Vec<CCA3>>, cca3: CCA3) {
+	if selected_signal.read().contains(&cca3) {
+		selected_signal.retain(|c| *c != cca3);
+	} else {
+		selected_signal.push(cca3);
+	}
+}
+
+fn cycle_status(mut status_signal: Signal<HashMap<Status, StatusFilter>>, status: Status) {
+	let mut statuses = status_signal.write();
+	match statuses.get(&status).copied().unwrap_or_default().cycle() {
+		StatusFilter::Any => statuses.remove(&status),
+		filter => statuses.insert(status, filter),
+	};
+}
+
 #[component]
-pub fn CountryList(segments: Vec<String>) -> Element {
-	let mut all_countries_signal = use_signal(|| Vec::<CountryOverview>::new());
-	let mut search_text_signal = use_signal(|| "".to_string());
-	let mut sort_by_signal = use_signal(|| SortBy::Population);
-	let filter_region_signal = use_signal(|| Vec::<Region>::new());
-	let mut filter_status_signal = use_signal(|| HashMap::from([
-		(Status::Independent, false),
-		(Status::UN, false)
-	]));
-	let mut page_signal = use_signal(|| HashMap::from([
-		(CURRENT_PAGE, 0 as usize),
-		(TOTAL_PAGES, 0 as usize)
-	]));
+pub fn CountryList(segments: Vec<String>, query: Box<ListQuery>) -> Element {
+	let (all_countries_signal, mut countries_resource) = use_overviews();
+	let mut search_text_signal = use_signal(|| query.search.clone());
+	let mut sort_signal = use_signal(|| query.sort.clone());
+	let mut filter_region_signal = use_signal(|| query.regions.clone());
+	let mut filter_subregion_signal = use_signal(|| query.subregions.clone());
+	let mut filter_status_signal = use_signal(|| query.statuses.clone());
+	let mut filter_population_signal = use_signal(|| query.population);
+	let mut filter_area_signal = use_signal(|| query.area);
+	let selected_signal = use_signal(Vec::<CCA3>::new);
+	let mut page_size_signal = use_signal(|| query.page_size);
+	let mut infinite_signal = use_signal(|| query.infinite);
+	let mut page_signal = use_signal(|| query.page);
 	let navigator = use_navigator();
 
 	*TITLE.write() = "Home".to_string();
 
-	let countries_resource = use_resource(move || async move {
-		Request::get(
-			&format!(
-				"https://restcountries.com/v3.1/all?fields=flags,name,population,area,region,subregion,cca3,independent,unMember"
-			))
-			.send()
-			.await
-			.unwrap()
-			.json::<Vec<CountryOverview>>()
-			.await
-			.unwrap()
+	let parsed_search = use_memo(move || ParsedQuery::parse(&search_text_signal.read()));
+
+	// A `sort:` typed into the search box replaces the chosen sort order
+	let shown_sort = use_memo(move || parsed_search.read().sort_or(&sort_signal.read()).to_vec());
+	let sort_overridden = use_memo(move || !parsed_search.read().sort.is_empty());
+
+	let subregions = use_memo(move || subregions_by_region(&all_countries_signal.read()));
+
+	// Subregions still filtering the list without their region selected, e.g. from a shared URL
+	let other_subregions = use_memo(move || {
+		let regions = filter_region_signal.read();
+		filter_subregion_signal
+			.read()
+			.iter()
+			.filter(|subregion| {
+				!subregions.read().iter().any(|(region, names)| regions.contains(region) && contains_subregion(names, subregion))
+			})
+			.cloned()
+			.collect::<Vec<_>>()
 	});
 
-	use_effect(move || {
-		if let Some(countries) = countries_resource.read().clone() {
-			all_countries_signal.set(countries);
-		}
+	// Everything but the paging, which depends on how many countries match
+	let filter_query = use_memo(move || ListQuery {
+		search: search_text_signal(),
+		sort: sort_signal(),
+		regions: filter_region_signal(),
+		subregions: filter_subregion_signal(),
+		statuses: filter_status_signal(),
+		population: filter_population_signal(),
+		area: filter_area_signal(),
+		..ListQuery::default()
 	});
 
-	let sorted_countries = use_memo(move || {
-    sort_data(all_countries_signal.read().clone(), &sort_by_signal.read())
+	let filtered_countries = use_memo(move || filter_query.read().apply(&all_countries_signal.read()));
+
+	let paginator = use_memo(move || {
+		Paginator::new(page_signal(), page_size_signal().rows(), filtered_countries.read().len())
 	});
 
-	let filtered_countries = use_memo(move || {
-		filter_data(&sorted_countries.read(), &[
-			FilterQuery::Text(&search_text_signal.read()),
-			FilterQuery::Region(&filter_region_signal.read()),
-			FilterQuery::Status(&filter_status_signal.read())
-		])
+	let list_query = use_memo(move || ListQuery {
+		page: paginator().page(),
+		page_size: page_size_signal(),
+		infinite: infinite_signal(),
+		..filter_query()
 	});
 
 	use_effect(move || {
-		let _filtere_countries = filtered_countries();
-		// Reset the pagination to page 0 when list of countries changes
-		page_signal.write().entry(CURRENT_PAGE).and_modify(|p| *p = 0);
+		let query = list_query();
+		navigator.replace(Route::CountryList { segments: segments.clone(), query: Box::new(query) });
 	});
 
+	// Pick up URL changes made outside this page, e.g. back/forward or the header link
+	use_effect(use_reactive!(|query| {
+		if *query != *list_query.peek() {
+			search_text_signal.set(query.search);
+			sort_signal.set(query.sort);
+			filter_region_signal.set(query.regions);
+			filter_subregion_signal.set(query.subregions);
+			filter_status_signal.set(query.statuses);
+			filter_population_signal.set(query.population);
+			filter_area_signal.set(query.area);
+			page_size_signal.set(query.page_size);
+			infinite_signal.set(query.infinite);
+			page_signal.set(query.page);
+		}
+	}));
+
+	// Reset the pagination to page 0 when the filters or sort order change
+	let mut reset_page = move || {
+		page_signal.set(0);
+	};
+
 	let count = use_memo(move || {
 		filtered_countries.read().len()
 	});
 
-	use_effect(move || {
-		let count = count();
-		if count == 0 {
-			page_signal.write().entry(TOTAL_PAGES).and_modify(|p| *p = 0);
-		} else {
-			let page_count = (count + PAGE_SIZE - 1) / PAGE_SIZE;
-			page_signal.write().entry(TOTAL_PAGES).and_modify(|p| *p = page_count);
-		}
-	});
-
+	// Infinite scroll keeps every page up to the current one on screen
 	let paginated_countries = use_memo(move || {
-		let start = page_signal().get(CURRENT_PAGE).unwrap() * PAGE_SIZE;
-		let end = (start + PAGE_SIZE).min(filtered_countries().len());
-		filtered_countries()[start..end].to_vec()
+		let paginator = paginator();
+		let range = if infinite_signal() { paginator.range_through() } else { paginator.range() };
+		filtered_countries.read()[range].to_vec()
 	});
 
+	let fetch_error = countries_resource().and_then(Result::err);
+
 	rsx! {
 		main {
 			class: "flex justify-center items-center h-max mt-[-4rem]",
@@ -146,6 +198,20 @@ pub fn CountryList(segments: Vec<String>) -> Element {
 						class: "text-lg font-semibold",
 						"Found {count} countries"
 					}
+					Link {
+						class: "bg-dark rounded-md px-4 py-2 ml-auto mr-3",
+						to: Route::Map { query: Box::new(list_query()) },
+						"Map view"
+					}
+					button {
+						class: "bg-interact rounded-md px-4 py-2 cursor-pointer disabled:bg-dark disabled:cursor-default mr-5",
+						disabled: selected_signal.read().len() < 2,
+						title: "Select at least two countries to compare",
+						onclick: move |_| {
+							navigator.push(Route::Compare { codes: CCA3List(selected_signal()) });
+						},
+						"Compare selected ({selected_signal.read().len()})"
+					}
 					div {
 						class: "bg-dark rounded-md p-2",
 						label {
@@ -157,9 +223,25 @@ pub fn CountryList(segments: Vec<String>) -> Element {
 						input {
 							class: "inline-block align-middle min-w-80",
 							value: "{search_text_signal}",
-							placeholder: "Search by Name, Region, Subregion",
-							oninput: move |event| search_text_signal.set(event.value()),
+							placeholder: "Search, or filter with region:europe pop>10m sort:-area",
+							title: "Filters: region:, subregion:, pop and area with > < = or min-max, un:yes, independent:no, sort:-density. Quote values with spaces.",
+							oninput: move |event| {
+								search_text_signal.set(event.value());
+								reset_page();
+							},
 						}
+						for error in parsed_search.read().errors.iter() {
+							span {
+								key: "{error.span.start}",
+								class: "block text-xs text-red-500 mt-1",
+								"{error}"
+							}
+						}
+					}
+				}
+				if fetch_error.is_some() && !all_countries_signal.is_empty() {
+					OfflineNotice {
+						on_retry: move |_| countries_resource.restart()
 					}
 				}
 				div {
@@ -171,11 +253,55 @@ pub fn CountryList(segments: Vec<String>) -> Element {
 								class: "block text-xs",
 								"Sort by"
 							}
+							div {
+								class: "flex flex-row gap-2",
+								select {
+									class: "grow border-2 border-dark rounded-md p-2 disabled:opacity-50",
+									disabled: sort_overridden(),
+									title: if sort_overridden() { "Sorted by the sort: in the search" },
+									value: "{shown_sort.read()[0].by}",
+									oninput: move |event| {
+										set_sort_key(sort_signal, 0, SortBy::from_str(&event.value()).ok());
+										reset_page();
+									},
+									for sort in SortBy::iter() {
+										option {
+											value: "{sort}",
+											"{sort}",
+										}
+									}
+								}
+								button {
+									class: "border-2 border-dark rounded-md px-3 cursor-pointer disabled:cursor-default disabled:opacity-50",
+									disabled: sort_overridden(),
+									title: "{shown_sort.read()[0].direction}",
+									onclick: move |_| {
+										let primary = sort_signal.read()[0].by;
+										sort_by_column(sort_signal, primary, false);
+										reset_page();
+									},
+									"{shown_sort.read()[0].direction.indicator()}"
+								}
+							}
+						}
+						div {
+							label {
+								class: "block text-xs",
+								"Then by"
+							}
 							select {
-								class: "w-full border-2 border-dark rounded-md p-2",
-								value: "{sort_by_signal}",
-								oninput: move |event| sort_by_signal.set(SortBy::from_str(&event.value()).unwrap()),
-								for sort in SortBy::iter() {
+								class: "w-full border-2 border-dark rounded-md p-2 disabled:opacity-50",
+								disabled: sort_overridden(),
+								value: shown_sort.read().get(1).map(|key| key.by.to_string()).unwrap_or_default(),
+								oninput: move |event| {
+									set_sort_key(sort_signal, 1, SortBy::from_str(&event.value()).ok());
+									reset_page();
+								},
+								option {
+									value: "",
+									"None",
+								}
+								for sort in SortBy::iter().filter(|sort| *sort != shown_sort.read()[0].by) {
 									option {
 										value: "{sort}",
 										"{sort}",
@@ -200,11 +326,80 @@ pub fn CountryList(segments: Vec<String>) -> Element {
 												id: "{region}",
 												r#type: "checkbox",
 												value: "{region}",
-												oninput: move |_| toggle_region(filter_region_signal, region),
+												checked: filter_region_signal.read().contains(&region),
+												oninput: move |_| {
+													let deselected = filter_region_signal.read().contains(&region);
+													toggle_region(&mut filter_region_signal.write(), region);
+													// Drop the subregions of a deselected region, keeping any others from the URL
+													if deselected {
+														let subregions = subregions.read();
+														if let Some((_, names)) = subregions.iter().find(|(r, _)| *r == region) {
+															filter_subregion_signal.retain(|subregion| !contains_subregion(names, subregion));
+														}
+													}
+													reset_page();
+												},
 											}
 										}
 								}
 							}
+							for (region, names) in subregions().into_iter().filter(|(region, _)| filter_region_signal.read().contains(region)) {
+								div {
+									class: "mt-2 ml-2 pl-2 border-l-2 border-dark",
+									span {
+										class: "block text-xs text-light",
+										"{region} subregions"
+									}
+									div {
+										class: "flex flex-row flex-wrap gap-x-2 gap-y-1",
+										for name in names {
+											label {
+												class: "has-checked:bg-dark cursor-pointer px-2 py-1 rounded-md text-sm",
+												input {
+													class: "hidden",
+													r#type: "checkbox",
+													value: "{name}",
+													checked: contains_subregion(&filter_subregion_signal.read(), &name),
+													oninput: {
+														let name = name.clone();
+														move |_| {
+															toggle_subregion(&mut filter_subregion_signal.write(), &name);
+															reset_page();
+														}
+													},
+												}
+												"{name}"
+											}
+										}
+									}
+								}
+							}
+							if !other_subregions.read().is_empty() {
+								div {
+									class: "mt-2 ml-2 pl-2 border-l-2 border-dark",
+									span {
+										class: "block text-xs text-light",
+										"Other subregions"
+									}
+									div {
+										class: "flex flex-row flex-wrap gap-x-2 gap-y-1",
+										for name in other_subregions() {
+											button {
+												class: "bg-dark cursor-pointer px-2 py-1 rounded-md text-sm",
+												title: "Stop filtering on {name}",
+												onclick: {
+													let name = name.clone();
+													move |_| {
+														toggle_subregion(&mut filter_subregion_signal.write(), &name);
+														reset_page();
+													}
+												},
+												"{name} ✕"
+											}
+										}
+									}
+								}
+							}
 						}
 						div {
 							label {
@@ -214,33 +409,64 @@ pub fn CountryList(segments: Vec<String>) -> Element {
 							div {
 								class: "flex flex-col gap-2",
 								for status in Status::iter() {
-									label {
-										class: "group cursor-pointer",
-										for: "{status}",
-										div {
-											class: "h-6 w-6 border-2 border-dark rounded-md inline-block align-middle mr-2 group-has-checked:border-interact group-has-checked:bg-interact",
-											img {
-												class: "hidden group-has-checked:block",
-												src: asset!("/assets/Done_round.svg")
-											}
-										}
-										input {
-											class: "hidden",
-											id: "{status}",
-											r#type: "checkbox",
-											checked: "{filter_status_signal.read().get(&status).unwrap()}",
-											oninput: move |_| {
-												filter_status_signal.write().entry(status).and_modify(|s| *s = !*s);
+									div {
+										class: "cursor-pointer select-none",
+										title: "Click to cycle between any, required and excluded",
+										onclick: move |_| {
+											cycle_status(filter_status_signal, status);
+											reset_page();
+										},
+										match filter_status_signal.read().get(&status).copied().unwrap_or_default() {
+											StatusFilter::Any => rsx! {
+												div {
+													class: "h-6 w-6 border-2 border-dark rounded-md inline-block align-middle mr-2",
+												}
+												span {
+													class: "align-middle",
+													"{status}"
+												}
+											},
+											StatusFilter::Yes => rsx! {
+												div {
+													class: "h-6 w-6 border-2 rounded-md inline-block align-middle mr-2 border-interact bg-interact",
+													img {
+														src: asset!("/assets/Done_round.svg")
+													}
+												}
+												span {
+													class: "align-middle",
+													"{status}"
+												}
+											},
+											StatusFilter::No => rsx! {
+												div {
+													class: "h-6 w-6 border-2 rounded-md inline-block align-middle mr-2 border-red-500 text-red-500 text-center leading-5",
+													"✕"
+												}
+												span {
+													class: "align-middle",
+													"Not {status}"
+												}
 											},
-										}
-										span {
-											class: "align-middle",
-											"{status}"
 										}
 									}
 								}
 							}
 						}
+						RangeInput {
+							label: "Population",
+							range_signal: filter_population_signal,
+							on_change: move |_| reset_page()
+						}
+						RangeInput {
+							label: "Area (km²)",
+							range_signal: filter_area_signal,
+							on_change: move |_| reset_page()
+						}
+						ExportPanel {
+							filtered: filtered_countries,
+							page: paginated_countries
+						}
 					}
 					section {
 						class: "grow",
@@ -249,37 +475,80 @@ pub fn CountryList(segments: Vec<String>) -> Element {
 							thead {
 								tr {
 									class: "text-left border-b border-dark",
+									th {
+										class: "pb-2 w-8"
+									}
 									th {
 										class: "pb-2 w-1/10",
 										"Flag"		
 									}
-									th {
-										class: "pb-2 w-[22.5%]",
-										"Name"
+									SortHeader {
+										label: "Name",
+										by: SortBy::Name,
+										sort: shown_sort,
+										overridden: sort_overridden(),
+										sort_signal,
+										on_sort: move |_| reset_page()
 									}
-									th {
-										class: "pb-2 w-[22.5%]",
-										"Population"
+									SortHeader {
+										label: "Population",
+										by: SortBy::Population,
+										sort: shown_sort,
+										overridden: sort_overridden(),
+										sort_signal,
+										on_sort: move |_| reset_page()
 									}
-									th {
-										class: "pb-2 w-[22.5%]",
-										"Area (km²)"
+									SortHeader {
+										label: "Area (km²)",
+										by: SortBy::Area,
+										sort: shown_sort,
+										overridden: sort_overridden(),
+										sort_signal,
+										on_sort: move |_| reset_page()
 									}
-									th {
-										class: "pb-2 w-[22.5%]",
-										"Region"
+									SortHeader {
+										label: "Region",
+										by: SortBy::Region,
+										sort: shown_sort,
+										overridden: sort_overridden(),
+										sort_signal,
+										on_sort: move |_| reset_page()
 									}
 								}
 							}
 							tbody {
-								if !paginated_countries().is_empty() {
+								if let Some(error) = fetch_error.clone().filter(|_| all_countries_signal.is_empty()) {
+									tr {
+										td {
+											colspan: "6",
+											ErrorPanel {
+												error,
+												on_retry: move |_| countries_resource.restart()
+											}
+										}
+									}
+								} else if !paginated_countries().is_empty() {
 									for country in paginated_countries().iter() {
 										tr {
 											class: "cursor-pointer hover:bg-dark",
 											onclick: {
-												let cca3 = country.cca3.clone();
-												move |_| { navigator.push(Route::CountryDetails { cca3: cca3 }); }
+												let cca3 = country.cca3;
+												move |_| { navigator.push(Route::CountryDetails { cca3 }); }
 											},
+											td {
+												class: "py-2",
+												onclick: move |event| event.stop_propagation(),
+												input {
+													class: "cursor-pointer accent-interact",
+													r#type: "checkbox",
+													title: "Select for comparison",
+													checked: selected_signal.read().contains(&country.cca3),
+													oninput: {
+														let cca3 = country.cca3;
+														move |_| toggle_selected(selected_signal, cca3)
+													}
+												}
+											}
 											td {
 												class: "py-2",
 												img {
@@ -310,13 +579,16 @@ pub fn CountryList(segments: Vec<String>) -> Element {
 									tr {
 										td {
 											class: "text-center py-2",
-											colspan: "5",
+											colspan: "6",
 											"No countries found. Try expanding your filters."
 										}
 									}
 								} else {
 									for _ in 0..5 {
 										tr {
+											td {
+												class: "py-2"
+											}
 											td {
 												class: "py-2",
 												div {
@@ -352,23 +624,54 @@ pub fn CountryList(segments: Vec<String>) -> Element {
 								}
 							}
 						}
+						if infinite_signal() {
+							if paginator().has_next() {
+								// Keyed by page so a fresh observer checks again whether more rows are needed
+								ScrollSentinel {
+									key: "{paginator().page()}",
+									on_visible: move |_| page_signal.set(paginator.peek().page() + 1)
+								}
+							}
+						} else {
+							Pagination {
+								paginator: paginator(),
+								on_select: move |page| page_signal.set(page)
+							}
+						}
 						div {
-							class: "w-full flex flex-row gap-2 justify-center text-center mt-3",
-							for page in 0..*page_signal().get(TOTAL_PAGES).unwrap() {
-								if page == *page_signal().get(CURRENT_PAGE).unwrap() {
-									span {
-										class: "bg-interact p-2 rounded-md w-6 h-6 box-content cursor-default",
-										"{page + 1}"
+							class: "w-full flex flex-row gap-5 justify-end items-center text-sm mt-3",
+							label {
+								"Rows per page"
+								select {
+									class: "border border-dark rounded-md p-1 ml-2",
+									value: "{page_size_signal}",
+									onchange: move |event| {
+										if let Ok(page_size) = PageSize::from_str(&event.value()) {
+											page_size_signal.set(page_size);
+											reset_page();
+										}
+									},
+									for page_size in PageSize::iter() {
+										option {
+											value: "{page_size}",
+											"{page_size}"
+										}
 									}
-								} else {
-									span {
-										class: "bg-dark p-2 rounded-md w-6 h-6 box-content cursor-pointer",
-										onclick: move |_| {
-											page_signal.write().entry(CURRENT_PAGE).and_modify(|p| *p = page);
-										},
-										"{page + 1}"
+								}
+							}
+							label {
+								class: "flex flex-row items-center gap-2 cursor-pointer",
+								input {
+									r#type: "checkbox",
+									class: "accent-interact",
+									disabled: page_size_signal() == PageSize::All,
+									checked: infinite_signal(),
+									oninput: move |event| {
+										infinite_signal.set(event.checked());
+										reset_page();
 									}
 								}
+								"Infinite scroll"
 							}
 						}
 					}
@@ -377,3 +680,86 @@ pub fn CountryList(segments: Vec<String>) -> Element {
 		}
 	}
 }
+
+/// Table header that sorts on click and adds a secondary sort key on shift-click. Shows `sort`,
+/// which only follows `sort_signal` while the search has no `sort:` of its own.
+#[component]
+fn SortHeader(
+	label: String,
+	by: SortBy,
+	sort: Memo<Vec<SortKey>>,
+	overridden: bool,
+	sort_signal: Signal<Vec<SortKey>>,
+	on_sort: EventHandler<()>
+) -> Element {
+	let position = sort.read().iter().position(|key| key.by == by);
+
+	rsx! {
+		th {
+			class: if overridden { "pb-2 w-[22.5%] select-none" } else { "pb-2 w-[22.5%] cursor-pointer select-none" },
+			title: if overridden { "Sorted by the sort: in the search" } else { "Click to sort, shift-click to add as a secondary sort" },
+			onclick: move |event| {
+				if !overridden {
+					sort_by_column(sort_signal, by, event.modifiers().shift());
+					on_sort.call(());
+				}
+			},
+			"{label}"
+			if let Some(i) = position {
+				span {
+					class: "ml-1 text-xs text-interact",
+					"{sort.read()[i].direction.indicator()}"
+					if sort.read().len() > 1 {
+						sup {
+							"{i + 1}"
+						}
+					}
+				}
+			}
+		}
+	}
+}
+
+/// Min/max number inputs for a range filter, an empty input leaves that side open.
+#[component]
+fn RangeInput(label: String, range_signal: Signal<ValueRange>, on_change: EventHandler<()>) -> Element {
+	let range = range_signal();
+	let bound = |value: String| value.trim().parse::<f64>().ok();
+
+	rsx! {
+		div {
+			span {
+				class: "block text-xs",
+				"{label}"
+			}
+			div {
+				class: "flex flex-row items-center gap-2",
+				input {
+					class: "w-full border-2 border-dark rounded-md p-2",
+					r#type: "number",
+					min: "0",
+					placeholder: "Min",
+					value: range.min.map(|min| min.to_string()).unwrap_or_default(),
+					oninput: move |event| {
+						range_signal.write().min = bound(event.value());
+						on_change.call(());
+					},
+				}
+				span {
+					"–"
+				}
+				input {
+					class: "w-full border-2 border-dark rounded-md p-2",
+					r#type: "number",
+					min: "0",
+					placeholder: "Max",
+					value: range.max.map(|max| max.to_string()).unwrap_or_default(),
+					oninput: move |event| {
+						range_signal.write().max = bound(event.value());
+						on_change.call(());
+					},
+				}
+			}
+		}
+	}
+}
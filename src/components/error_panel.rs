@@ -0,0 +1,70 @@
+use dioxus::prelude::*;
+use crate::{
+	api::ApiError,
+	types::CCA3,
+	Route
+};
+
+#[component]
+pub fn ErrorPanel(error: ApiError, on_retry: EventHandler<MouseEvent>) -> Element {
+	rsx! {
+		div {
+			class: "w-full flex flex-col items-center gap-3 p-5 text-center",
+			span {
+				class: "text-lg font-semibold",
+				"Something went wrong"
+			}
+			span {
+				class: "text-sm text-light",
+				"{error}"
+			}
+			button {
+				class: "bg-interact rounded-md px-4 py-2 cursor-pointer",
+				onclick: move |event| on_retry.call(event),
+				"Try again"
+			}
+		}
+	}
+}
+
+#[component]
+pub fn CountryNotFound(cca3: CCA3) -> Element {
+	rsx! {
+		div {
+			class: "w-full flex flex-col items-center gap-3 p-5 text-center",
+			h1 {
+				class: "text-4xl font-semibold",
+				"Country not found"
+			}
+			span {
+				class: "text-light",
+				"There is no country with the code \"{cca3}\"."
+			}
+			Link {
+				class: "bg-interact rounded-md px-4 py-2",
+				to: Route::CountryList {
+					segments: Vec::<String>::new(),
+					query: Box::default()
+				},
+				"Back to all countries"
+			}
+		}
+	}
+}
+
+#[component]
+pub fn OfflineNotice(on_retry: EventHandler<MouseEvent>) -> Element {
+	rsx! {
+		div {
+			class: "w-full flex flex-row justify-between items-center bg-dark rounded-md px-4 py-2 text-sm",
+			span {
+				"Couldn't reach the countries API, showing offline data."
+			}
+			button {
+				class: "text-interact cursor-pointer",
+				onclick: move |event| on_retry.call(event),
+				"Retry"
+			}
+		}
+	}
+}
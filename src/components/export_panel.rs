@@ -0,0 +1,73 @@
+use dioxus::prelude::*;
+use strum::IntoEnumIterator;
+use crate::{
+	export::{save_file, ExportError, ExportFormat},
+	types::CountryOverview
+};
+
+/// Export buttons for the filtered countries, or only the page being shown.
+#[component]
+pub fn ExportPanel(filtered: Memo<Vec<CountryOverview>>, page: Memo<Vec<CountryOverview>>) -> Element {
+	let mut page_only_signal = use_signal(|| false);
+	let mut error_signal = use_signal(|| None::<ExportError>);
+
+	let export = move |format: ExportFormat| async move {
+		let countries = if page_only_signal() { page() } else { filtered() };
+		let file_name = format!("countries.{}", format.extension());
+		let result = match format.encode(&countries) {
+			Ok(bytes) => save_file(&file_name, format.mime_type(), &bytes).await,
+			Err(error) => Err(error),
+		};
+		error_signal.set(result.err());
+	};
+
+	rsx! {
+		div {
+			span {
+				class: "block text-xs",
+				"Export"
+			}
+			div {
+				class: "flex flex-row gap-2 text-sm mt-1",
+				label {
+					class: "has-checked:bg-dark cursor-pointer p-2 rounded-md",
+					input {
+						class: "hidden",
+						r#type: "radio",
+						name: "export-scope",
+						checked: !page_only_signal(),
+						oninput: move |_| page_only_signal.set(false),
+					}
+					"All {filtered.read().len()} results"
+				}
+				label {
+					class: "has-checked:bg-dark cursor-pointer p-2 rounded-md",
+					input {
+						class: "hidden",
+						r#type: "radio",
+						name: "export-scope",
+						checked: page_only_signal(),
+						oninput: move |_| page_only_signal.set(true),
+					}
+					"This page"
+				}
+			}
+			div {
+				class: "flex flex-row gap-2 mt-2",
+				for format in ExportFormat::iter() {
+					button {
+						class: "grow bg-dark rounded-md px-3 py-2 cursor-pointer",
+						onclick: move |_| export(format),
+						"{format}"
+					}
+				}
+			}
+			if let Some(error) = error_signal() {
+				span {
+					class: "block text-xs text-red-400 mt-2",
+					"{error}"
+				}
+			}
+		}
+	}
+}
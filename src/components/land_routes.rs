@@ -0,0 +1,295 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use dioxus::prelude::*;
+use crate::{
+	components::{use_overviews, ErrorPanel, OfflineNotice},
+	geo::BorderGraph,
+	types::{CountryOverview, RouteQuery, CCA3},
+	Route, TITLE
+};
+
+/// How many countries the neighbour ranking lists.
+const RANKING_SIZE: usize = 10;
+
+/// Shortest land route between two countries, plus rankings drawn from the same border graph.
+#[component]
+pub fn LandRoutes(query: RouteQuery) -> Element {
+	let (all_countries_signal, mut countries_resource) = use_overviews();
+	let navigator = use_navigator();
+
+	*TITLE.write() = "Land routes".to_string();
+
+	let graph = use_memo(move || BorderGraph::new(&all_countries_signal.read()));
+
+	let countries_by_code = use_memo(move || {
+		all_countries_signal
+			.read()
+			.iter()
+			.map(|c| (c.cca3, c.clone()))
+			.collect::<HashMap<CCA3, CountryOverview>>()
+	});
+
+	let sorted_countries = use_memo(move || {
+		let mut countries = all_countries_signal();
+		countries.sort_by(|a, b| a.name.common.cmp(&b.name.common));
+		countries
+	});
+
+	let path = match (query.from, query.to) {
+		(Some(from), Some(to)) => Some(graph.read().shortest_path(from, to)),
+		_ => None
+	};
+	// Both walk the whole graph, so they only run again when the countries change
+	let components = use_memo(move || {
+		let (landmasses, islands): (Vec<_>, Vec<_>) = graph
+			.read()
+			.components()
+			.into_iter()
+			.partition(|component| component.len() > 1);
+		(landmasses, islands.into_iter().flatten().collect::<Vec<_>>())
+	});
+	let ranking = use_memo(move || graph.read().degree_ranking().into_iter().take(RANKING_SIZE).collect::<Vec<_>>());
+	let (landmasses, islands) = components();
+
+	let name_of = move |cca3: CCA3| {
+		countries_by_code
+			.read()
+			.get(&cca3)
+			.map_or_else(|| cca3.to_string(), |c| c.name.common.clone())
+	};
+
+	let fetch_error = countries_resource().and_then(Result::err);
+
+	if let Some(error) = fetch_error.clone().filter(|_| all_countries_signal.is_empty()) {
+		return rsx! {
+			main {
+				class: "flex justify-center items-center h-max mt-[-4rem]",
+				div {
+					class: "w-[95%] xl:w-5/6 2xl:w-3/4 bg-darker border border-dark rounded-2xl",
+					ErrorPanel {
+						error,
+						on_retry: move |_| countries_resource.restart()
+					}
+				}
+			}
+		}
+	}
+
+	rsx! {
+		main {
+			class: "flex justify-center items-center h-max mt-[-4rem]",
+			div {
+				class: "w-[95%] xl:w-5/6 2xl:w-3/4 flex flex-col justify-center items-center bg-darker border border-dark rounded-2xl gap-y-5 p-5",
+				h2 {
+					class: "w-full text-lg font-semibold",
+					"Land routes"
+				}
+				if fetch_error.is_some() {
+					OfflineNotice {
+						on_retry: move |_| countries_resource.restart()
+					}
+				}
+				section {
+					class: "w-full flex flex-row flex-wrap items-end gap-5",
+					CountryPicker {
+						label: "From",
+						countries: sorted_countries(),
+						selected: query.from,
+						on_select: {
+							let query = query.clone();
+							move |from| {
+								navigator.replace(Route::LandRoutes { query: RouteQuery { from, ..query.clone() } });
+							}
+						}
+					}
+					button {
+						class: "bg-dark rounded-md px-4 py-2 cursor-pointer",
+						title: "Swap countries",
+						onclick: {
+							let query = query.clone();
+							move |_| {
+								navigator.replace(Route::LandRoutes { query: RouteQuery { from: query.to, to: query.from } });
+							}
+						},
+						"⇄"
+					}
+					CountryPicker {
+						label: "To",
+						countries: sorted_countries(),
+						selected: query.to,
+						on_select: {
+							let query = query.clone();
+							move |to| {
+								navigator.replace(Route::LandRoutes { query: RouteQuery { to, ..query.clone() } });
+							}
+						}
+					}
+				}
+				section {
+					class: "w-full bg-dark rounded-md p-5",
+					match (query.from, query.to, path) {
+						(Some(from), Some(to), _) if from == to => rsx! {
+							span {
+								"{name_of(from)} is both the start and the end of the route, so there are no borders to cross."
+							}
+						},
+						(Some(from), Some(to), Some(Some(path))) => rsx! {
+							span {
+								class: "block text-lg font-semibold mb-3",
+								match path.len() - 1 {
+									1 => "{name_of(from)} → {name_of(to)}: 1 crossing".to_string(),
+									crossings => format!("{} → {}: {crossings} crossings", name_of(from), name_of(to)),
+								}
+							}
+							div {
+								class: "flex flex-row flex-wrap items-center gap-2",
+								for (i, cca3) in path.into_iter().enumerate() {
+									if i > 0 {
+										span {
+											class: "text-light",
+											"→"
+										}
+									}
+									CountryChip {
+										cca3,
+										country: countries_by_code.read().get(&cca3).cloned()
+									}
+								}
+							}
+						},
+						(Some(from), Some(to), _) => rsx! {
+							span {
+								"There is no land route between {name_of(from)} and {name_of(to)}."
+							}
+						},
+						_ => rsx! {
+							span {
+								class: "text-light",
+								"Pick two countries to find the fewest border crossings between them."
+							}
+						}
+					}
+				}
+				div {
+					class: "w-full flex flex-col xl:flex-row gap-5",
+					section {
+						class: "w-full xl:w-1/3",
+						h3 {
+							class: "font-semibold mb-2",
+							"Most land neighbours"
+						}
+						ol {
+							for (cca3, degree) in ranking() {
+								li {
+									class: "border-b border-dark flex flex-row justify-between py-2",
+									Link {
+										to: Route::CountryDetails { cca3 },
+										"{name_of(cca3)}"
+									}
+									span {
+										"{degree}"
+									}
+								}
+							}
+						}
+					}
+					section {
+						class: "w-full xl:w-2/3 flex flex-col gap-2",
+						h3 {
+							class: "font-semibold",
+							"Connected by land"
+						}
+						span {
+							class: "text-sm text-light",
+							"{landmasses.len()} groups of countries share land borders, and {islands.len()} countries have no land neighbours."
+						}
+						for component in landmasses {
+							details {
+								class: "border-b border-dark py-2",
+								summary {
+									class: "cursor-pointer",
+									"{component.len()} countries, including {name_of(component[0])}"
+								}
+								div {
+									class: "flex flex-row flex-wrap gap-2 pt-2",
+									for cca3 in component {
+										CountryChip {
+											cca3,
+											country: countries_by_code.read().get(&cca3).cloned()
+										}
+									}
+								}
+							}
+						}
+						details {
+							class: "border-b border-dark py-2",
+							summary {
+								class: "cursor-pointer",
+								"No land neighbours ({islands.len()})"
+							}
+							div {
+								class: "flex flex-row flex-wrap gap-2 pt-2",
+								for cca3 in islands {
+									CountryChip {
+										cca3,
+										country: countries_by_code.read().get(&cca3).cloned()
+									}
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+	}
+}
+
+#[component]
+fn CountryPicker(label: String, countries: Vec<CountryOverview>, selected: Option<CCA3>, on_select: EventHandler<Option<CCA3>>) -> Element {
+	rsx! {
+		div {
+			label {
+				class: "block text-xs",
+				"{label}"
+			}
+			select {
+				class: "border border-dark rounded-md p-2 mt-1 min-w-60",
+				value: selected.map(|cca3| cca3.to_string()).unwrap_or_default(),
+				onchange: move |event| on_select.call(CCA3::from_str(&event.value()).ok()),
+				option {
+					value: "",
+					"Choose a country"
+				}
+				for country in countries {
+					option {
+						value: "{country.cca3}",
+						"{country.name.common}"
+					}
+				}
+			}
+		}
+	}
+}
+
+#[component]
+fn CountryChip(cca3: CCA3, country: Option<CountryOverview>) -> Element {
+	rsx! {
+		Link {
+			class: "flex flex-row items-center gap-2 bg-darker rounded-md px-2 py-1",
+			to: Route::CountryDetails { cca3 },
+			if let Some(country) = country {
+				img {
+					class: "h-4 rounded-sm",
+					src: "{country.flags.svg}"
+				}
+				span {
+					"{country.name.common}"
+				}
+			} else {
+				span {
+					"{cca3}"
+				}
+			}
+		}
+	}
+}
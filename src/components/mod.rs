@@ -1,7 +1,25 @@
 mod wrapper;
+mod overviews;
+mod error_panel;
 mod country_details;
 mod country_list;
+mod compare;
+mod world_map;
+mod land_routes;
+mod stats;
+mod export_panel;
+mod scroll_sentinel;
+mod pagination;
 
 pub use wrapper::*;
+pub use overviews::*;
+pub use error_panel::*;
 pub use country_details::*;
-pub use country_list::*;
\ No newline at end of file
+pub use country_list::*;
+pub use compare::*;
+pub use world_map::*;
+pub use land_routes::*;
+pub use stats::*;
+pub use export_panel::*;
+pub use scroll_sentinel::*;
+pub use pagination::*;
\ No newline at end of file
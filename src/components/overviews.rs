@@ -0,0 +1,36 @@
+use dioxus::prelude::*;
+use crate::{
+	api::{ApiError, CountriesClient, Snapshot, CACHE},
+	types::CountryOverview
+};
+
+pub type OverviewsResource = Resource<Result<Vec<CountryOverview>, ApiError>>;
+
+/// Every country overview, starting from the cache or the offline snapshot and replaced once
+/// the API responds. The resource is returned so pages can show fetch errors and retry.
+pub fn use_overviews() -> (Signal<Vec<CountryOverview>>, OverviewsResource) {
+	let mut all_countries_signal = use_signal(|| {
+		// Pre-rendered pages hydrate from the snapshot they were built with; the cache follows
+		// as soon as the resource resolves
+		if cfg!(feature = "fullstack") {
+			return Snapshot::overviews();
+		}
+		CACHE.peek().overviews().map(<[_]>::to_vec).unwrap_or_else(Snapshot::overviews)
+	});
+	let client = use_context::<CountriesClient>();
+
+	let countries_resource = use_resource(move || {
+		let client = client.clone();
+		async move {
+			client.all_overviews().await
+		}
+	});
+
+	use_effect(move || {
+		if let Some(Ok(countries)) = countries_resource.read().clone() {
+			all_countries_signal.set(countries);
+		}
+	});
+
+	(all_countries_signal, countries_resource)
+}
@@ -0,0 +1,79 @@
+use dioxus::prelude::*;
+use crate::types::Paginator;
+
+/// Pager with first/previous/next/last buttons that collapses long runs of pages into gaps.
+/// Hidden when everything fits on one page.
+#[component]
+pub fn Pagination(paginator: Paginator, on_select: EventHandler<usize>) -> Element {
+	let current = paginator.page();
+	let last = paginator.last_page();
+	let backward = [
+		("«", "First page", 0),
+		("‹", "Previous page", current.saturating_sub(1)),
+	];
+	let forward = [
+		("›", "Next page", (current + 1).min(last)),
+		("»", "Last page", last),
+	];
+
+	rsx! {
+		if paginator.total_pages() > 1 {
+			div {
+				class: "w-full flex flex-row gap-2 justify-center text-center mt-3",
+				for (symbol, title, page) in backward {
+					PageButton {
+						label: symbol,
+						title,
+						disabled: !paginator.has_previous(),
+						on_click: move |_| on_select.call(page)
+					}
+				}
+				for page in paginator.visible_pages() {
+					match page {
+						Some(page) if page == current => rsx! {
+							span {
+								class: "bg-interact p-2 rounded-md w-6 h-6 box-content cursor-default",
+								"{page + 1}"
+							}
+						},
+						Some(page) => rsx! {
+							PageButton {
+								label: "{page + 1}",
+								title: "Page {page + 1}",
+								disabled: false,
+								on_click: move |_| on_select.call(page)
+							}
+						},
+						None => rsx! {
+							span {
+								class: "p-2 w-6 h-6 box-content text-light",
+								"…"
+							}
+						}
+					}
+				}
+				for (symbol, title, page) in forward {
+					PageButton {
+						label: symbol,
+						title,
+						disabled: !paginator.has_next(),
+						on_click: move |_| on_select.call(page)
+					}
+				}
+			}
+		}
+	}
+}
+
+#[component]
+fn PageButton(label: String, title: String, disabled: bool, on_click: EventHandler<()>) -> Element {
+	rsx! {
+		button {
+			class: "bg-dark p-2 rounded-md w-6 h-6 box-content cursor-pointer disabled:opacity-40 disabled:cursor-default",
+			title,
+			disabled,
+			onclick: move |_| on_click.call(()),
+			"{label}"
+		}
+	}
+}
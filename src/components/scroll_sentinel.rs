@@ -0,0 +1,77 @@
+use dioxus::prelude::*;
+
+/// Invisible marker below a list that calls `on_visible` each time it scrolls into view,
+/// used to load the next page of an infinite list.
+#[component]
+pub fn ScrollSentinel(on_visible: EventHandler<()>) -> Element {
+	// Dropping the observer with the component stops it firing for a list that is gone
+	#[cfg(feature = "web")]
+	let observer = use_hook(|| std::rc::Rc::new(std::cell::RefCell::new(None::<observer::Observer>)));
+
+	rsx! {
+		div {
+			class: "h-4 w-full",
+			onmounted: move |_event| {
+				#[cfg(feature = "web")]
+				if let Some(element) = _event.data().downcast::<web_sys::Element>() {
+					*observer.borrow_mut() = observer::Observer::new(element, on_visible);
+				}
+			}
+		}
+	}
+}
+
+#[cfg(feature = "web")]
+mod observer {
+	use dioxus::prelude::*;
+	use dioxus::dioxus_core::{current_scope_id, Runtime};
+	use js_sys::Array;
+	use web_sys::{
+		wasm_bindgen::{closure::Closure, JsCast},
+		Element,
+		IntersectionObserver,
+		IntersectionObserverEntry,
+		IntersectionObserverInit
+	};
+
+	/// Starts loading a little before the marker is actually on screen.
+	const ROOT_MARGIN: &str = "200px";
+
+	pub struct Observer {
+		observer: IntersectionObserver,
+		_callback: Closure<dyn FnMut(Array)>,
+	}
+
+	impl Observer {
+		pub fn new(element: &Element, on_visible: EventHandler<()>) -> Option<Self> {
+			// The browser calls back outside of rendering, so re-enter the component's scope to run the handler
+			let runtime = Runtime::current().ok()?;
+			let scope = current_scope_id().ok()?;
+
+			let callback = Closure::<dyn FnMut(Array)>::new(move |entries: Array| {
+				let visible = entries
+					.iter()
+					.any(|entry| entry.unchecked_into::<IntersectionObserverEntry>().is_intersecting());
+				if visible {
+					runtime.on_scope(scope, || on_visible.call(()));
+				}
+			});
+
+			let options = IntersectionObserverInit::new();
+			options.set_root_margin(ROOT_MARGIN);
+			let observer = IntersectionObserver::new_with_options(callback.as_ref().unchecked_ref(), &options).ok()?;
+			observer.observe(element);
+
+			Some(Observer {
+				observer,
+				_callback: callback,
+			})
+		}
+	}
+
+	impl Drop for Observer {
+		fn drop(&mut self) {
+			self.observer.disconnect();
+		}
+	}
+}
@@ -0,0 +1,238 @@
+use std::str::FromStr;
+use dioxus::prelude::*;
+use strum::IntoEnumIterator;
+use crate::{
+	components::{use_overviews, ErrorPanel, OfflineNotice},
+	geo::compact_number,
+	stats::{region_stats, GroupStats, StatsMetric},
+	types::{ListQuery, Region},
+	Route, TITLE
+};
+use thousands::Separable;
+
+/// Height of one bar in the chart, in SVG user units.
+const BAR_HEIGHT: f64 = 8.0;
+
+#[component]
+pub fn Stats() -> Element {
+	let (all_countries_signal, mut countries_resource) = use_overviews();
+	let mut metric_signal = use_signal(StatsMetric::default);
+	let mut expanded_signal = use_signal(Vec::<Region>::new);
+
+	*TITLE.write() = "Statistics".to_string();
+
+	let regions = use_memo(move || region_stats(&all_countries_signal.read()));
+	let fetch_error = countries_resource().and_then(Result::err);
+
+	if let Some(error) = fetch_error.clone().filter(|_| all_countries_signal.is_empty()) {
+		return rsx! {
+			main {
+				class: "flex justify-center items-center h-max mt-[-4rem]",
+				div {
+					class: "w-[95%] xl:w-5/6 2xl:w-3/4 bg-darker border border-dark rounded-2xl",
+					ErrorPanel {
+						error,
+						on_retry: move |_| countries_resource.restart()
+					}
+				}
+			}
+		}
+	}
+
+	let bars = regions
+		.read()
+		.iter()
+		.map(|r| (r.stats.name.clone(), metric_signal().value(&r.stats), filtered_list(r.region, None)))
+		.collect::<Vec<_>>();
+
+	rsx! {
+		main {
+			class: "flex justify-center items-center h-max mt-[-4rem]",
+			div {
+				class: "w-[95%] xl:w-5/6 2xl:w-3/4 flex flex-col justify-center items-center bg-darker border border-dark rounded-2xl gap-y-5 p-5",
+				section {
+					class: "w-full flex flex-row justify-between items-end",
+					h2 {
+						class: "text-lg font-semibold",
+						"Statistics by region"
+					}
+					div {
+						label {
+							class: "block text-xs",
+							"Chart"
+						}
+						select {
+							class: "border border-dark rounded-md p-2 mt-1",
+							value: "{metric_signal}",
+							onchange: move |event| {
+								if let Ok(metric) = StatsMetric::from_str(&event.value()) {
+									metric_signal.set(metric);
+								}
+							},
+							for metric in StatsMetric::iter() {
+								option {
+									value: "{metric}",
+									"{metric}"
+								}
+							}
+						}
+					}
+				}
+				if fetch_error.is_some() {
+					OfflineNotice {
+						on_retry: move |_| countries_resource.restart()
+					}
+				}
+				if regions.read().is_empty() {
+					div {
+						class: "w-full h-60 bg-dark rounded-md animate-pulse"
+					}
+				} else {
+					BarChart { bars }
+				}
+				table {
+					class: "w-full",
+					thead {
+						tr {
+							class: "text-left border-b border-dark",
+							th { class: "pb-2", "Region" }
+							th { class: "pb-2", "Countries" }
+							th { class: "pb-2", "Population" }
+							th { class: "pb-2", "Median population" }
+							th { class: "pb-2", "Area (km²)" }
+							th { class: "pb-2", "Density (/km²)" }
+							th { class: "pb-2", "Independent" }
+							th { class: "pb-2", "UN members" }
+						}
+					}
+					tbody {
+						for region in regions() {
+							StatsRow {
+								stats: region.stats.clone(),
+								to: filtered_list(region.region, None),
+								expanded: Some(expanded_signal.read().contains(&region.region)),
+								on_toggle: move |_| {
+									if expanded_signal.read().contains(&region.region) {
+										expanded_signal.retain(|r| *r != region.region);
+									} else {
+										expanded_signal.push(region.region);
+									}
+								}
+							}
+							if expanded_signal.read().contains(&region.region) {
+								for subregion in region.subregions.iter() {
+									StatsRow {
+										stats: subregion.clone(),
+										to: filtered_list(region.region, Some(subregion.name.clone())),
+										expanded: None
+									}
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+	}
+}
+
+/// The country list filtered on a region, and optionally one of its subregions.
+fn filtered_list(region: Region, subregion: Option<String>) -> Route {
+	Route::CountryList {
+		segments: Vec::new(),
+		query: Box::new(ListQuery {
+			regions: vec![region],
+			subregions: subregion.into_iter().collect(),
+			..ListQuery::default()
+		})
+	}
+}
+
+/// Horizontal bars scaled to the largest value, each linking to `Route`.
+#[component]
+fn BarChart(bars: Vec<(String, f64, Route)>) -> Element {
+	let navigator = use_navigator();
+	let max = bars.iter().map(|(_, value, _)| *value).fold(0.0, f64::max);
+	let height = bars.len() as f64 * BAR_HEIGHT;
+	let bars = bars
+		.into_iter()
+		.map(|(label, value, to)| {
+			let width = if max > 0.0 { value / max * 70.0 } else { 0.0 };
+			(label, value, width, to)
+		})
+		.collect::<Vec<_>>();
+
+	rsx! {
+		svg {
+			class: "w-full h-auto",
+			view_box: "0 0 100 {height}",
+			for (i, (label, value, width, to)) in bars.into_iter().enumerate() {
+				g {
+					class: "cursor-pointer hover:opacity-80",
+					onclick: move |_| { navigator.push(to.clone()); },
+					text {
+						class: "fill-lighter",
+						x: "0",
+						y: "{i as f64 * BAR_HEIGHT + BAR_HEIGHT / 2.0}",
+						font_size: "3",
+						dominant_baseline: "middle",
+						"{label}"
+					}
+					rect {
+						class: "fill-interact",
+						x: "18",
+						y: "{i as f64 * BAR_HEIGHT + 1.5}",
+						width: "{width}",
+						height: "{BAR_HEIGHT - 3.0}",
+						rx: "1"
+					}
+					text {
+						class: "fill-light",
+						x: "{19.0 + width}",
+						y: "{i as f64 * BAR_HEIGHT + BAR_HEIGHT / 2.0}",
+						font_size: "3",
+						dominant_baseline: "middle",
+						"{compact_number(value)}"
+					}
+				}
+			}
+		}
+	}
+}
+
+/// A table row for a region, which can be expanded, or one of its subregions when `expanded` is `None`.
+#[component]
+fn StatsRow(stats: GroupStats, to: Route, expanded: Option<bool>, on_toggle: Option<EventHandler<MouseEvent>>) -> Element {
+	rsx! {
+		tr {
+			class: "border-b border-dark",
+			td {
+				class: if expanded.is_some() { "py-2" } else { "py-2 pl-6 text-light" },
+				if let Some(expanded) = expanded {
+					button {
+						class: "cursor-pointer mr-2 w-4",
+						title: if expanded { "Hide subregions" } else { "Show subregions" },
+						onclick: move |event| {
+							if let Some(on_toggle) = on_toggle {
+								on_toggle.call(event);
+							}
+						},
+						if expanded { "▾" } else { "▸" }
+					}
+				}
+				Link {
+					class: "hover:text-interact",
+					to,
+					"{stats.name}"
+				}
+			}
+			td { class: "py-2", "{stats.count}" }
+			td { class: "py-2", "{stats.total_population.separate_with_commas()}" }
+			td { class: "py-2", "{(stats.median_population.round() as u64).separate_with_commas()}" }
+			td { class: "py-2", "{(stats.total_area.round() as u64).separate_with_commas()}" }
+			td { class: "py-2", "{stats.density():.1}" }
+			td { class: "py-2", "{stats.independent}" }
+			td { class: "py-2", "{stats.un_members}" }
+		}
+	}
+}
@@ -0,0 +1,266 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use dioxus::prelude::*;
+use strum::IntoEnumIterator;
+use crate::{
+	components::{use_overviews, ErrorPanel, OfflineNotice},
+	geo::{class_opacity, compact_number, Classes, Metric, Scale, MAP_HEIGHT, MAP_WIDTH, SHAPES},
+	types::{CountryOverview, ListQuery, CCA3},
+	Route, TITLE
+};
+use thousands::Separable;
+
+/// World map of the countries matching the same filters as the list, linked through the URL query.
+#[component]
+pub fn Map(query: Box<ListQuery>) -> Element {
+	let (all_countries_signal, mut countries_resource) = use_overviews();
+	let mut hovered_signal = use_signal(|| None::<(CCA3, f64, f64)>);
+	// `None` colours countries by whether they match the filters alone
+	let mut metric_signal = use_signal(|| None::<Metric>);
+	let mut scale_signal = use_signal(Scale::default);
+	let navigator = use_navigator();
+
+	*TITLE.write() = "Map".to_string();
+
+	let countries_by_code = use_memo(move || {
+		all_countries_signal
+			.read()
+			.iter()
+			.map(|c| (c.cca3, c.clone()))
+			.collect::<HashMap<CCA3, CountryOverview>>()
+	});
+
+	let matching = use_memo(use_reactive!(|query| {
+		query
+			.apply(&all_countries_signal.read())
+			.into_iter()
+			.map(|c| c.cca3)
+			.collect::<HashSet<_>>()
+	}));
+
+	// Classes only span the matching countries, so narrowing the filters sharpens the contrast
+	let classes = use_memo(move || {
+		let metric = metric_signal()?;
+		let values = matching
+			.read()
+			.iter()
+			.filter_map(|cca3| countries_by_code.read().get(cca3).and_then(|c| metric.value(c)))
+			.collect::<Vec<_>>();
+		Classes::new(scale_signal(), &values)
+	});
+
+	let fill = move |cca3: CCA3| -> (&'static str, f64) {
+		let countries = countries_by_code.read();
+		let Some(country) = countries.get(&cca3) else {
+			return ("fill-dark stroke-darker", 0.4);
+		};
+		if !matching.read().contains(&cca3) {
+			return ("fill-dark stroke-darker cursor-pointer hover:opacity-80", 1.0);
+		}
+		match (metric_signal(), classes()) {
+			(Some(metric), Some(classes)) => match metric.value(country) {
+				Some(value) => ("fill-interact stroke-darker cursor-pointer hover:opacity-80", class_opacity(classes.class_of(value))),
+				None => ("fill-light stroke-darker cursor-pointer hover:opacity-80", 1.0),
+			},
+			_ => ("fill-interact stroke-darker cursor-pointer hover:opacity-80", 1.0),
+		}
+	};
+
+	let fetch_error = countries_resource().and_then(Result::err);
+	let hovered = hovered_signal().and_then(|(cca3, x, y)| {
+		countries_by_code.read().get(&cca3).cloned().map(|country| (country, x, y))
+	});
+
+	rsx! {
+		main {
+			class: "flex justify-center items-center h-max mt-[-4rem]",
+			div {
+				class: "w-[95%] xl:w-5/6 2xl:w-3/4 flex flex-col justify-center items-center bg-darker border border-dark rounded-2xl gap-y-5 p-5",
+				section {
+					class: "w-full flex flex-row justify-between items-center",
+					h2 {
+						class: "text-lg font-semibold",
+						"Found {matching.read().len()} countries"
+					}
+					Link {
+						class: "bg-dark rounded-md px-4 py-2",
+						to: Route::CountryList {
+							segments: Vec::new(),
+							query: query.clone()
+						},
+						"Table view"
+					}
+				}
+				section {
+					class: "w-full flex flex-row flex-wrap gap-5",
+					div {
+						label {
+							class: "block text-xs",
+							"Colour by"
+						}
+						select {
+							class: "border border-dark rounded-md p-2 mt-1",
+							value: metric_signal().map(|m| m.to_string()).unwrap_or_default(),
+							onchange: move |event| metric_signal.set(Metric::from_str(&event.value()).ok()),
+							option {
+								value: "",
+								"Matching filters"
+							}
+							for metric in Metric::iter() {
+								option {
+									value: "{metric}",
+									"{metric}"
+								}
+							}
+						}
+					}
+					if metric_signal().is_some() {
+						div {
+							label {
+								class: "block text-xs",
+								"Scale"
+							}
+							select {
+								class: "border border-dark rounded-md p-2 mt-1",
+								value: "{scale_signal}",
+								onchange: move |event| {
+									if let Ok(scale) = Scale::from_str(&event.value()) {
+										scale_signal.set(scale);
+									}
+								},
+								for scale in Scale::iter() {
+									option {
+										value: "{scale}",
+										"{scale}"
+									}
+								}
+							}
+						}
+					}
+				}
+				if let Some(error) = fetch_error.clone().filter(|_| all_countries_signal.is_empty()) {
+					ErrorPanel {
+						error,
+						on_retry: move |_| countries_resource.restart()
+					}
+				} else if SHAPES.is_empty() {
+					span {
+						class: "text-light p-5",
+						"The map shapes were not bundled with this build."
+					}
+				} else {
+					if fetch_error.is_some() {
+						OfflineNotice {
+							on_retry: move |_| countries_resource.restart()
+						}
+					}
+					svg {
+						class: "w-full h-auto",
+						view_box: "0 0 {MAP_WIDTH} {MAP_HEIGHT}",
+						onmouseleave: move |_| hovered_signal.set(None),
+						for shape in SHAPES.iter() {
+							path {
+								key: "{shape.cca3}",
+								d: "{shape.path}",
+								stroke_width: "0.2",
+								class: fill(shape.cca3).0,
+								fill_opacity: fill(shape.cca3).1,
+								onclick: {
+									let cca3 = shape.cca3;
+									move |_| {
+										if countries_by_code.read().contains_key(&cca3) {
+											navigator.push(Route::CountryDetails { cca3 });
+										}
+									}
+								},
+								onmousemove: {
+									let cca3 = shape.cca3;
+									move |event: MouseEvent| {
+										let point = event.client_coordinates();
+										hovered_signal.set(Some((cca3, point.x, point.y)));
+									}
+								}
+							}
+						}
+					}
+				}
+				if let (Some(metric), Some(classes)) = (metric_signal(), classes()) {
+					Legend { metric, classes }
+				}
+				if let Some((country, x, y)) = hovered {
+					div {
+						class: "fixed pointer-events-none z-10 bg-dark border border-darker rounded-md px-3 py-2 text-sm",
+						style: "left: {x + 12.0}px; top: {y + 12.0}px;",
+						span {
+							class: "block font-semibold",
+							"{country.name.common}"
+						}
+						span {
+							class: "block",
+							"Population: {country.population.separate_with_commas()}"
+						}
+						span {
+							class: "block",
+							"Area: {country.area.separate_with_commas()} km²"
+						}
+						if metric_signal() == Some(Metric::Density) {
+							span {
+								class: "block",
+								"Density: {country.density():.1} /km²"
+							}
+						}
+					}
+				}
+			}
+		}
+	}
+}
+
+#[component]
+fn Legend(metric: Metric, classes: Classes) -> Element {
+	rsx! {
+		section {
+			class: "w-full flex flex-row flex-wrap items-center gap-3 text-sm",
+			span {
+				class: "text-light",
+				"{metric}"
+			}
+			for (class, (low, high)) in classes.ranges().enumerate() {
+				div {
+					class: "flex flex-row items-center gap-1",
+					svg {
+						class: "w-4 h-4",
+						view_box: "0 0 1 1",
+						rect {
+							class: "fill-interact",
+							width: "1",
+							height: "1",
+							fill_opacity: class_opacity(class)
+						}
+					}
+					span {
+						"{compact_number(low)} – {compact_number(high)}{metric.unit()}"
+					}
+				}
+			}
+			div {
+				class: "flex flex-row items-center gap-1",
+				span {
+					class: "w-4 h-4 bg-light rounded-sm"
+				}
+				span {
+					"No data"
+				}
+			}
+			div {
+				class: "flex flex-row items-center gap-1",
+				span {
+					class: "w-4 h-4 bg-dark rounded-sm"
+				}
+				span {
+					"Not matching"
+				}
+			}
+		}
+	}
+}
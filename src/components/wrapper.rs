@@ -1,5 +1,5 @@
 use dioxus::prelude::*;
-use crate::Route;
+use crate::{types::RouteQuery, Route};
 
 #[component]
 pub fn Wrapper() -> Element {
@@ -15,10 +15,28 @@ fn Header() -> Element {
 	rsx! {
 		div {
 			background_image: "url({asset!(\"/assets/hero-image.jpg\")})",
-			class: "w-full h-[300px] flex bg-cover z-1",
+			class: "w-full h-[300px] flex bg-cover z-1 relative",
+			nav {
+				class: "absolute top-4 right-6 flex flex-row gap-5 text-sm",
+				Link {
+					to: Route::Map { query: Box::default() },
+					"Map"
+				}
+				Link {
+					to: Route::LandRoutes { query: RouteQuery::default() },
+					"Land routes"
+				}
+				Link {
+					to: Route::Stats {},
+					"Statistics"
+				}
+			}
 			Link {
 				class: "m-auto",
-				to: Route::CountryList { segments: Vec::<String>::new() },
+				to: Route::CountryList {
+					segments: Vec::<String>::new(),
+					query: Box::default()
+				},
 				img {
 					src: asset!("/assets/Logo.svg")
 				}
@@ -0,0 +1,88 @@
+use std::{collections::BTreeMap, path::PathBuf};
+use serde_json::{Map, Value};
+
+/// REST Countries only accepts up to 10 fields per request, so records are fetched in groups
+/// and merged on `cca3`, one group per line. `scripts/update-snapshot.sh` reads the same file.
+const FIELD_GROUPS: &str = include_str!("../scripts/snapshot-fields.txt");
+/// Upper bound on each upstream request, so a stalled mirror fails instead of hanging.
+#[cfg(not(target_arch = "wasm32"))]
+const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Where the command-line tools get full country records from.
+pub enum Source {
+	/// A JSON array of records in the format of `data/countries.json`, read without any network.
+	File(PathBuf),
+	/// REST Countries or a mirror of it.
+	Upstream(String),
+}
+
+impl Source {
+	/// Every country record, sorted by `cca3`.
+	pub fn load(&self) -> Result<Vec<Value>, String> {
+		let mut records = match self {
+			Source::File(path) => {
+				let bytes = std::fs::read(path).map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+				serde_json::from_slice::<Vec<Value>>(&bytes).map_err(|e| format!("{} is not a list of countries: {e}", path.display()))?
+			},
+			Source::Upstream(base_url) => fetch_upstream(base_url)?
+		};
+		records.sort_by(|a, b| cca3(a).cmp(cca3(b)));
+		Ok(records)
+	}
+}
+
+impl std::fmt::Display for Source {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Source::File(path) => write!(f, "{}", path.display()),
+			Source::Upstream(base_url) => write!(f, "{base_url}"),
+		}
+	}
+}
+
+pub fn cca3(record: &Value) -> &str {
+	record.get("cca3").and_then(Value::as_str).unwrap_or_default()
+}
+
+fn fetch_upstream(base_url: &str) -> Result<Vec<Value>, String> {
+	let mut merged = BTreeMap::<String, Map<String, Value>>::new();
+
+	for (group, fields) in FIELD_GROUPS.lines().filter(|line| !line.trim().is_empty()).enumerate() {
+		let url = format!("{}/all?fields={}", base_url.trim_end_matches('/'), fields.trim());
+		let records = serde_json::from_slice::<Vec<Map<String, Value>>>(&get(&url)?)
+			.map_err(|e| format!("Unexpected response from {url}: {e}"))?;
+		for record in records {
+			let Some(code) = record.get("cca3").and_then(Value::as_str).map(str::to_string) else {
+				continue;
+			};
+			// Like the snapshot, only countries listed by the first group are kept
+			match merged.get_mut(&code) {
+				Some(existing) => existing.extend(record),
+				None if group == 0 => {
+					merged.insert(code, record);
+				},
+				None => {}
+			}
+		}
+	}
+
+	Ok(merged.into_values().map(Value::Object).collect())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn get(url: &str) -> Result<Vec<u8>, String> {
+	let agent = ureq::Agent::new_with_config(
+		ureq::Agent::config_builder().timeout_global(Some(TIMEOUT)).build()
+	);
+	agent
+		.get(url)
+		.call()
+		.and_then(|mut response| response.body_mut().read_to_vec())
+		.map_err(|e| format!("Could not fetch {url}: {e}"))
+}
+
+/// The browser app fetches through its own client, so only native tools reach upstream here.
+#[cfg(target_arch = "wasm32")]
+fn get(url: &str) -> Result<Vec<u8>, String> {
+	Err(format!("Could not fetch {url}: loading a dataset from upstream needs a native build"))
+}
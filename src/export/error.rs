@@ -0,0 +1,23 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportError {
+	/// The countries could not be written in the chosen format.
+	Encode(String),
+	/// The file could not be handed to the browser or written to disk.
+	Save(String),
+	/// This platform has no way to save files.
+	Unsupported,
+}
+
+impl fmt::Display for ExportError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ExportError::Encode(message) => write!(f, "Couldn't create the export file: {message}"),
+			ExportError::Save(message) => write!(f, "Couldn't save the export file: {message}"),
+			ExportError::Unsupported => write!(f, "Exporting isn't supported on this platform"),
+		}
+	}
+}
+
+impl std::error::Error for ExportError {}
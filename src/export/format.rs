@@ -0,0 +1,102 @@
+use rust_xlsxwriter::{Format, Workbook};
+use serde::Serialize;
+use strum_macros::{Display, EnumIter, EnumString};
+use crate::{export::ExportError, types::CountryOverview};
+
+/// Column headings, in the same order as the country table.
+const HEADERS: [&str; 5] = ["Flag", "Name", "Population", "Area (km²)", "Region"];
+
+#[derive(EnumIter, Display, EnumString, PartialEq, Debug, Copy, Clone)]
+#[strum(ascii_case_insensitive)]
+pub enum ExportFormat {
+	#[strum(to_string = "CSV")]
+	Csv,
+	#[strum(to_string = "JSON")]
+	Json,
+	#[strum(serialize = "xlsx", to_string = "Excel")]
+	Xlsx,
+}
+
+impl ExportFormat {
+	pub fn extension(self) -> &'static str {
+		match self {
+			ExportFormat::Csv => "csv",
+			ExportFormat::Json => "json",
+			ExportFormat::Xlsx => "xlsx",
+		}
+	}
+
+	pub fn mime_type(self) -> &'static str {
+		match self {
+			ExportFormat::Csv => "text/csv",
+			ExportFormat::Json => "application/json",
+			ExportFormat::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+		}
+	}
+
+	/// Serializes countries as a file in this format, one row per country.
+	pub fn encode(self, countries: &[CountryOverview]) -> Result<Vec<u8>, ExportError> {
+		let rows = countries.iter().map(Row::from).collect::<Vec<_>>();
+		match self {
+			ExportFormat::Csv => encode_csv(&rows),
+			ExportFormat::Json => serde_json::to_vec_pretty(&rows).map_err(|e| ExportError::Encode(e.to_string())),
+			ExportFormat::Xlsx => encode_xlsx(&rows),
+		}
+	}
+}
+
+/// A country as it appears in the table, with the flag as a link to its image.
+#[derive(Serialize)]
+struct Row<'a> {
+	flag: &'a str,
+	name: &'a str,
+	population: u32,
+	area: f32,
+	region: String,
+}
+
+impl<'a> From<&'a CountryOverview> for Row<'a> {
+	fn from(country: &'a CountryOverview) -> Self {
+		Row {
+			flag: &country.flags.svg,
+			name: &country.name.common,
+			population: country.population,
+			area: country.area,
+			region: country.region.to_string(),
+		}
+	}
+}
+
+fn encode_csv(rows: &[Row]) -> Result<Vec<u8>, ExportError> {
+	let mut writer = csv::Writer::from_writer(Vec::new());
+	writer.write_record(HEADERS).map_err(|e| ExportError::Encode(e.to_string()))?;
+	for row in rows {
+		writer
+			.write_record([row.flag, row.name, &row.population.to_string(), &row.area.to_string(), &row.region])
+			.map_err(|e| ExportError::Encode(e.to_string()))?;
+	}
+	writer.into_inner().map_err(|e| ExportError::Encode(e.to_string()))
+}
+
+fn encode_xlsx(rows: &[Row]) -> Result<Vec<u8>, ExportError> {
+	let mut workbook = Workbook::new();
+	let worksheet = workbook.add_worksheet();
+	let bold = Format::new().set_bold();
+	let thousands = Format::new().set_num_format("#,##0");
+	let to_error = |e: rust_xlsxwriter::XlsxError| ExportError::Encode(e.to_string());
+
+	for (col, header) in HEADERS.iter().enumerate() {
+		worksheet.write_string_with_format(0, col as u16, *header, &bold).map_err(to_error)?;
+	}
+	for (i, row) in rows.iter().enumerate() {
+		let line = i as u32 + 1;
+		worksheet.write_string(line, 0, row.flag).map_err(to_error)?;
+		worksheet.write_string(line, 1, row.name).map_err(to_error)?;
+		worksheet.write_number_with_format(line, 2, row.population, &thousands).map_err(to_error)?;
+		worksheet.write_number_with_format(line, 3, row.area, &thousands).map_err(to_error)?;
+		worksheet.write_string(line, 4, &row.region).map_err(to_error)?;
+	}
+	worksheet.autofit();
+
+	workbook.save_to_buffer().map_err(to_error)
+}
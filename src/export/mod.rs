@@ -0,0 +1,7 @@
+mod error;
+mod format;
+mod save;
+
+pub use error::*;
+pub use format::*;
+pub use save::*;
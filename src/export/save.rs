@@ -0,0 +1,69 @@
+use crate::export::ExportError;
+
+/// How long a download's object URL is kept, long enough for any browser to start reading it.
+#[cfg(feature = "web")]
+const REVOKE_DELAY_MS: i32 = 60_000;
+
+/// Hands a file to the user: a download in the browser, a save dialog on desktop.
+/// Resolves to `Ok(false)` when the user cancels the dialog.
+#[cfg(feature = "web")]
+pub async fn save_file(file_name: &str, mime_type: &str, bytes: &[u8]) -> Result<bool, ExportError> {
+	use js_sys::{Array, Uint8Array};
+	use web_sys::{
+		wasm_bindgen::{closure::Closure, JsCast, JsValue},
+		Blob,
+		BlobPropertyBag,
+		HtmlAnchorElement,
+		Url
+	};
+
+	let to_error = |e: JsValue| ExportError::Save(format!("{e:?}"));
+
+	let parts = Array::of1(&Uint8Array::from(bytes));
+	let options = BlobPropertyBag::new();
+	options.set_type(mime_type);
+	let blob = Blob::new_with_u8_array_sequence_and_options(&parts, &options).map_err(to_error)?;
+	let url = Url::create_object_url_with_blob(&blob).map_err(to_error)?;
+
+	let window = web_sys::window().ok_or(ExportError::Unsupported)?;
+	let document = window.document().ok_or(ExportError::Unsupported)?;
+	let anchor = document
+		.create_element("a")
+		.map_err(to_error)?
+		.dyn_into::<HtmlAnchorElement>()
+		.map_err(|_| ExportError::Unsupported)?;
+	anchor.set_href(&url);
+	anchor.set_download(file_name);
+	anchor.click();
+
+	// Firefox and Safari start the download after `click` returns and cancel it if the URL is
+	// already revoked, so it is released once the download has had time to begin
+	let revoke = Closure::once_into_js(move || {
+		let _ = Url::revoke_object_url(&url);
+	});
+	window
+		.set_timeout_with_callback_and_timeout_and_arguments_0(revoke.unchecked_ref(), REVOKE_DELAY_MS)
+		.map_err(to_error)?;
+	Ok(true)
+}
+
+#[cfg(all(feature = "desktop", not(feature = "web")))]
+pub async fn save_file(file_name: &str, _mime_type: &str, bytes: &[u8]) -> Result<bool, ExportError> {
+	let extension = file_name.rsplit('.').next().unwrap_or_default();
+	let Some(handle) = rfd::AsyncFileDialog::new()
+		.set_file_name(file_name)
+		.add_filter(extension.to_uppercase(), &[extension])
+		.save_file()
+		.await
+	else {
+		return Ok(false);
+	};
+
+	handle.write(bytes).await.map_err(|e| ExportError::Save(e.to_string()))?;
+	Ok(true)
+}
+
+#[cfg(not(any(feature = "web", feature = "desktop")))]
+pub async fn save_file(_file_name: &str, _mime_type: &str, _bytes: &[u8]) -> Result<bool, ExportError> {
+	Err(ExportError::Unsupported)
+}
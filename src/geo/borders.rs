@@ -0,0 +1,198 @@
+use std::collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap, VecDeque};
+use crate::types::{CountryOverview, CCA3};
+
+/// Undirected graph of land borders between countries, built from every country's `borders`.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct BorderGraph {
+	/// Sorted so paths, components and rankings come out the same on every run.
+	edges: BTreeMap<CCA3, BTreeSet<CCA3>>,
+}
+
+impl BorderGraph {
+	pub fn new(countries: &[CountryOverview]) -> Self {
+		let mut edges = countries
+			.iter()
+			.map(|c| (c.cca3, BTreeSet::new()))
+			.collect::<BTreeMap<_, _>>();
+
+		for country in countries {
+			for &neighbour in country.borders.iter().flatten() {
+				// Borders are listed on both sides, but a one-sided entry still counts as a crossing
+				edges.entry(country.cca3).or_default().insert(neighbour);
+				edges.entry(neighbour).or_default().insert(country.cca3);
+			}
+		}
+
+		BorderGraph { edges }
+	}
+
+	/// Fewest border crossings from `from` to `to`, both included, or `None` when no land route exists.
+	pub fn shortest_path(&self, from: CCA3, to: CCA3) -> Option<Vec<CCA3>> {
+		if !self.edges.contains_key(&from) || !self.edges.contains_key(&to) {
+			return None;
+		}
+
+		let mut previous = HashMap::from([(from, from)]);
+		let mut queue = VecDeque::from([from]);
+
+		while let Some(current) = queue.pop_front() {
+			if current == to {
+				let mut path = vec![to];
+				let mut step = to;
+				while step != from {
+					step = previous[&step];
+					path.push(step);
+				}
+				path.reverse();
+				return Some(path);
+			}
+
+			for &neighbour in &self.edges[&current] {
+				if let Entry::Vacant(entry) = previous.entry(neighbour) {
+					entry.insert(current);
+					queue.push_back(neighbour);
+				}
+			}
+		}
+
+		None
+	}
+
+	/// Groups of countries connected by land, largest first. Countries without land
+	/// neighbours form a group of their own.
+	pub fn components(&self) -> Vec<Vec<CCA3>> {
+		let mut seen = BTreeSet::new();
+		let mut components = Vec::new();
+
+		for &start in self.edges.keys() {
+			if !seen.insert(start) {
+				continue;
+			}
+
+			let mut component = vec![start];
+			let mut stack = vec![start];
+			while let Some(current) = stack.pop() {
+				for &neighbour in &self.edges[&current] {
+					if seen.insert(neighbour) {
+						component.push(neighbour);
+						stack.push(neighbour);
+					}
+				}
+			}
+			component.sort();
+			components.push(component);
+		}
+
+		// Stable, so groups of the same size stay in code order
+		components.sort_by_key(|component| std::cmp::Reverse(component.len()));
+		components
+	}
+
+	/// Countries by number of land neighbours, most first.
+	pub fn degree_ranking(&self) -> Vec<(CCA3, usize)> {
+		let mut ranking = self.edges
+			.iter()
+			.map(|(&cca3, neighbours)| (cca3, neighbours.len()))
+			.collect::<Vec<_>>();
+		ranking.sort_by_key(|&(_, degree)| std::cmp::Reverse(degree));
+		ranking
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::str::FromStr;
+	use serde_json::json;
+	use super::*;
+
+	fn code(cca3: &str) -> CCA3 {
+		CCA3::from_str(cca3).unwrap()
+	}
+
+	fn codes(list: &[&str]) -> Vec<CCA3> {
+		list.iter().map(|cca3| code(cca3)).collect()
+	}
+
+	fn graph(borders: &[(&str, &[&str])]) -> BorderGraph {
+		let countries = borders
+			.iter()
+			.map(|(cca3, neighbours)| {
+				serde_json::from_value::<CountryOverview>(json!({
+					"name": { "common": cca3, "official": cca3 },
+					"cca3": cca3,
+					"independent": true,
+					"unMember": true,
+					"region": "Europe",
+					"subregion": "",
+					"area": 1.0,
+					"population": 1,
+					"flags": { "png": "", "svg": "", "alt": null },
+					"borders": neighbours
+				}))
+				.unwrap()
+			})
+			.collect::<Vec<_>>();
+		BorderGraph::new(&countries)
+	}
+
+	/// AAA reaches DDD in three crossings through BBB and CCC, or two through EEE.
+	/// FFF and GGG only border each other, and HHH borders nobody.
+	fn world() -> BorderGraph {
+		graph(&[
+			("AAA", &["BBB", "EEE"]),
+			("BBB", &["AAA", "CCC"]),
+			("CCC", &["BBB", "DDD"]),
+			("DDD", &["CCC", "EEE"]),
+			("EEE", &["AAA", "DDD"]),
+			("FFF", &["GGG"]),
+			("GGG", &[]),
+			("HHH", &[]),
+		])
+	}
+
+	#[test]
+	fn finds_the_route_with_the_fewest_crossings() {
+		let graph = world();
+
+		assert_eq!(graph.shortest_path(code("AAA"), code("DDD")), Some(codes(&["AAA", "EEE", "DDD"])));
+		assert_eq!(graph.shortest_path(code("CCC"), code("AAA")), Some(codes(&["CCC", "BBB", "AAA"])));
+	}
+
+	#[test]
+	fn counts_borders_listed_on_one_side_only() {
+		assert_eq!(world().shortest_path(code("GGG"), code("FFF")), Some(codes(&["GGG", "FFF"])));
+	}
+
+	#[test]
+	fn a_route_to_the_same_country_has_no_crossings() {
+		assert_eq!(world().shortest_path(code("AAA"), code("AAA")), Some(codes(&["AAA"])));
+	}
+
+	#[test]
+	fn has_no_route_between_separate_landmasses_or_unknown_countries() {
+		let graph = world();
+
+		assert_eq!(graph.shortest_path(code("AAA"), code("FFF")), None);
+		assert_eq!(graph.shortest_path(code("HHH"), code("AAA")), None);
+		assert_eq!(graph.shortest_path(code("AAA"), code("ZZZ")), None);
+	}
+
+	#[test]
+	fn groups_countries_by_landmass_largest_first() {
+		assert_eq!(world().components(), [
+			codes(&["AAA", "BBB", "CCC", "DDD", "EEE"]),
+			codes(&["FFF", "GGG"]),
+			codes(&["HHH"]),
+		]);
+		assert!(BorderGraph::default().components().is_empty());
+	}
+
+	#[test]
+	fn ranks_countries_by_neighbour_count() {
+		let ranking = world().degree_ranking();
+
+		assert_eq!(ranking.len(), 8);
+		assert!(ranking[..5].iter().all(|&(_, degree)| degree == 2));
+		assert_eq!(ranking[5..], [(code("FFF"), 1), (code("GGG"), 1), (code("HHH"), 0)]);
+	}
+}
@@ -0,0 +1,116 @@
+use strum_macros::{Display, EnumIter, EnumString};
+use crate::types::CountryOverview;
+
+/// Number of colour steps a choropleth is divided into.
+pub const CLASSES: usize = 5;
+
+/// Country figure a choropleth is coloured by.
+#[derive(EnumIter, Display, EnumString, PartialEq, Default, Debug, Copy, Clone)]
+pub enum Metric {
+	#[default]
+	Population,
+	Area,
+	Density,
+}
+
+impl Metric {
+	/// `None` when the figure is missing, e.g. the density of a country without an area.
+	pub fn value(self, country: &CountryOverview) -> Option<f64> {
+		let value = match self {
+			Metric::Population => country.population as f64,
+			Metric::Area => country.area as f64,
+			Metric::Density => country.density() as f64,
+		};
+		Some(value).filter(|v| v.is_finite() && *v >= 0.0)
+	}
+
+	pub fn unit(self) -> &'static str {
+		match self {
+			Metric::Population => "",
+			Metric::Area => " km²",
+			Metric::Density => " /km²",
+		}
+	}
+}
+
+/// How values are divided into colour classes.
+#[derive(EnumIter, Display, EnumString, PartialEq, Default, Debug, Copy, Clone)]
+pub enum Scale {
+	/// Equal steps between the smallest and largest value.
+	#[default]
+	Linear,
+	/// Equal steps in orders of magnitude, so a few giants don't wash out everyone else.
+	#[strum(to_string = "Logarithmic")]
+	Log,
+	/// The same number of countries in every class.
+	Quantile,
+}
+
+/// Class boundaries for a set of values, from the smallest value to the largest.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Classes {
+	edges: Vec<f64>,
+}
+
+impl Classes {
+	/// Returns `None` when there are no values to classify.
+	pub fn new(scale: Scale, values: &[f64]) -> Option<Self> {
+		let mut sorted = values.iter().copied().filter(|v| v.is_finite()).collect::<Vec<_>>();
+		sorted.sort_by(f64::total_cmp);
+		let (&min, &max) = (sorted.first()?, sorted.last()?);
+
+		let edges = match scale {
+			Scale::Linear => (0..=CLASSES)
+				.map(|i| min + (max - min) * i as f64 / CLASSES as f64)
+				.collect(),
+			Scale::Log => {
+				// Zero has no logarithm, so the lowest class starts at the smallest positive value
+				let low = sorted.iter().copied().find(|v| *v > 0.0).unwrap_or(1.0).log10();
+				let high = max.max(f64::MIN_POSITIVE).log10().max(low);
+				let mut edges = (0..=CLASSES)
+					.map(|i| 10_f64.powf(low + (high - low) * i as f64 / CLASSES as f64))
+					.collect::<Vec<_>>();
+				edges[0] = min;
+				edges[CLASSES] = max;
+				edges
+			},
+			Scale::Quantile => (0..=CLASSES)
+				.map(|i| sorted[(sorted.len() - 1) * i / CLASSES])
+				.collect(),
+		};
+
+		Some(Classes { edges })
+	}
+
+	/// Index of the class `value` falls into, from 0 for the lowest to `CLASSES - 1`.
+	pub fn class_of(&self, value: f64) -> usize {
+		self.edges[1..CLASSES].iter().filter(|edge| value >= **edge).count()
+	}
+
+	/// Lower and upper bound of each class, lowest first.
+	pub fn ranges(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+		self.edges.windows(2).map(|edge| (edge[0], edge[1]))
+	}
+}
+
+/// Fill opacity for a class, so higher classes are drawn in a stronger colour.
+pub fn class_opacity(class: usize) -> f64 {
+	0.15 + 0.85 * class as f64 / (CLASSES - 1) as f64
+}
+
+/// Shortens a number for legends, e.g. `1.4B`, `83.2M`, `357k` or `4.5`.
+pub fn compact_number(value: f64) -> String {
+	let (scaled, suffix) = match value.abs() {
+		v if v >= 1e9 => (value / 1e9, "B"),
+		v if v >= 1e6 => (value / 1e6, "M"),
+		v if v >= 1e3 => (value / 1e3, "k"),
+		_ => (value, ""),
+	};
+
+	let text = if scaled.abs() >= 100.0 {
+		format!("{scaled:.0}")
+	} else {
+		format!("{scaled:.1}")
+	};
+	format!("{}{suffix}", text.strip_suffix(".0").unwrap_or(&text))
+}
@@ -0,0 +1,7 @@
+mod borders;
+mod choropleth;
+mod shapes;
+
+pub use borders::*;
+pub use choropleth::*;
+pub use shapes::*;
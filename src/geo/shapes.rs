@@ -0,0 +1,89 @@
+use std::fmt::Write;
+use std::sync::LazyLock;
+use serde::Deserialize;
+use crate::types::CCA3;
+
+/// Width and height of the projected map in SVG user units.
+pub const MAP_WIDTH: f64 = 360.0;
+pub const MAP_HEIGHT: f64 = 150.0;
+/// Latitudes north of this are drawn at the top edge of the map.
+const NORTH: f64 = 85.0;
+
+/// Country outlines bundled at build time from `data/world.geojson`.
+pub static SHAPES: LazyLock<Vec<Shape>> = LazyLock::new(|| {
+	serde_json::from_slice::<FeatureCollection>(include_bytes!(concat!(env!("OUT_DIR"), "/world.geojson")))
+		.map(|collection| {
+			collection.features
+				.into_iter()
+				.filter_map(|feature| Some(Shape {
+					cca3: feature.properties.cca3?,
+					path: feature.geometry?.to_path(),
+				}))
+				.collect()
+		})
+		.unwrap_or_default()
+});
+
+/// A country outline as an SVG path, projected onto a `MAP_WIDTH` by `MAP_HEIGHT` canvas.
+#[derive(Clone, PartialEq)]
+pub struct Shape {
+	pub cca3: CCA3,
+	pub path: String,
+}
+
+#[derive(Deserialize)]
+struct FeatureCollection {
+	features: Vec<Feature>,
+}
+
+#[derive(Deserialize)]
+struct Feature {
+	properties: Properties,
+	geometry: Option<Geometry>,
+}
+
+#[derive(Deserialize)]
+struct Properties {
+	/// Shapes without a code REST Countries knows, such as disputed areas, are skipped.
+	#[serde(deserialize_with = "lenient_cca3")]
+	cca3: Option<CCA3>,
+}
+
+/// Outer ring first, followed by any holes, as `[longitude, latitude]` pairs.
+type Polygon = Vec<Vec<[f64; 2]>>;
+
+#[derive(Deserialize)]
+#[serde(tag = "type", content = "coordinates")]
+enum Geometry {
+	Polygon(Polygon),
+	MultiPolygon(Vec<Polygon>),
+}
+
+impl Geometry {
+	fn to_path(&self) -> String {
+		let polygons = match self {
+			Geometry::Polygon(polygon) => std::slice::from_ref(polygon),
+			Geometry::MultiPolygon(polygons) => polygons.as_slice(),
+		};
+
+		let mut path = String::new();
+		for ring in polygons.iter().flatten() {
+			for (i, &[lng, lat]) in ring.iter().enumerate() {
+				let (x, y) = project(lng, lat);
+				let command = if i == 0 { 'M' } else { 'L' };
+				let _ = write!(path, "{command}{x:.1},{y:.1}");
+			}
+			path.push('Z');
+		}
+		path
+	}
+}
+
+/// Equirectangular projection with the far north and south cropped off.
+pub fn project(lng: f64, lat: f64) -> (f64, f64) {
+	(lng + 180.0, (NORTH - lat).clamp(0.0, MAP_HEIGHT))
+}
+
+fn lenient_cca3<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<CCA3>, D::Error> {
+	Ok(Option::<String>::deserialize(deserializer)?.and_then(|code| code.parse().ok()))
+}
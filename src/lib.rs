@@ -0,0 +1,6 @@
+//! Country data, search and export shared by the WorldRanks app and its command-line tools.
+
+pub mod dataset;
+pub mod export;
+
+pub use world_ranks_core::{query, search, types};
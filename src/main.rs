@@ -1,42 +1,86 @@
+mod api;
 mod components;
-mod types;
+mod geo;
+mod stats;
 
 use dioxus::prelude::*;
-use components::{Wrapper, CountryDetails, CountryList};
-use types::CCA3;
+use world_ranks::{export, query, search, types};
+use api::CountriesClient;
+use components::{Wrapper, Compare, CountryDetails, CountryList, LandRoutes, Map, Stats};
+use types::{CCA3, CCA3List, ListQuery, RouteQuery};
 
 #[derive(Routable, PartialEq, Clone)]
 enum Route {
 	#[layout(Wrapper)]
+	#[route("/compare/:codes")]
+	Compare { codes: CCA3List },
+	#[route("/map?:..query")]
+	Map { query: Box<ListQuery> },
+	#[route("/routes?:..query")]
+	LandRoutes { query: RouteQuery },
+	#[route("/stats")]
+	Stats {},
 	#[route("/:cca3")]
 	CountryDetails { cca3: CCA3 },
-	#[route("/:..segments")]
+	#[route("/:..segments?:..query")]
 	CountryList {
-		segments: Vec<String>
+		segments: Vec<String>,
+		query: Box<ListQuery>,
 	},
 }
 
 pub static TITLE:GlobalSignal<String> = Signal::global(|| "Home".to_string());
 
 fn main() {
+	// With `fullstack`, `dx bundle --ssg` writes every page from `static_routes` into `public`
+	#[cfg(feature = "fullstack")]
+	dioxus::LaunchBuilder::new()
+		.with_cfg(server_only! {
+			ServeConfig::builder()
+				.incremental(
+					dioxus::server::IncrementalRendererConfig::new()
+						// Next to the server binary as `dx bundle` lays it out, else the working directory
+						.static_dir(
+							std::env::current_exe()
+								.ok()
+								.and_then(|exe| Some(exe.parent()?.join("public")))
+								.unwrap_or_else(|| "public".into())
+						)
+						.clear_cache(false)
+				)
+				.enable_out_of_order_streaming()
+		})
+		.launch(App);
+
+	#[cfg(not(feature = "fullstack"))]
 	dioxus::launch(App);
 }
 
+/// Pages pre-rendered at build time: the list and every country in the offline snapshot.
+#[cfg(feature = "fullstack")]
+#[server(endpoint = "static_routes", output = server_fn::codec::Json)]
+async fn static_routes() -> Result<Vec<String>, ServerFnError> {
+	let list = Route::CountryList {
+		segments: Vec::new(),
+		query: Box::default()
+	};
+	let countries = api::Snapshot::overviews()
+		.into_iter()
+		.map(|country| Route::CountryDetails { cca3: country.cca3 });
+
+	Ok(std::iter::once(list).chain(countries).map(|route| route.to_string()).collect())
+}
+
 const FAVICON: Asset = asset!("assets/favicon.ico");
 const TAILWIND_CSS: Asset = asset!("assets/tailwind.css");
 
 #[component]
 fn App() -> Element {
-	use_effect(|| {
-		let page_title = TITLE();
-		if let Some(window) = web_sys::window() {
-			window.document().unwrap().set_title(format!(
-				"{page_title} | WorldRanks"
-			).as_str());
-		};
-	});
+	use_context_provider(CountriesClient::default);
 
+	// Rendered rather than set from an effect, so pages pre-rendered with `--ssg` carry their title
 	rsx! {
+		document::Title { "{TITLE} | WorldRanks" }
 		document::Link {
 			rel: "icon",
 			href: FAVICON,
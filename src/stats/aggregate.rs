@@ -0,0 +1,225 @@
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString};
+use crate::types::{CountryOverview, Region};
+
+/// Figures for one region or subregion.
+#[derive(PartialEq, Debug, Clone)]
+pub struct GroupStats {
+	pub name: String,
+	pub count: usize,
+	pub total_population: u64,
+	pub median_population: f64,
+	pub total_area: f64,
+	pub independent: usize,
+	pub un_members: usize,
+}
+
+impl GroupStats {
+	pub fn new(name: &str, countries: &[&CountryOverview]) -> Self {
+		let mut populations = countries.iter().map(|c| c.population).collect::<Vec<_>>();
+		populations.sort_unstable();
+		let median_population = match populations.len() {
+			0 => 0.0,
+			len if len % 2 == 0 => (populations[len / 2 - 1] as f64 + populations[len / 2] as f64) / 2.0,
+			len => populations[len / 2] as f64,
+		};
+
+		GroupStats {
+			name: name.to_string(),
+			count: countries.len(),
+			total_population: populations.iter().map(|&p| p as u64).sum(),
+			median_population,
+			// A country without an area leaves it out of the total instead of making it NaN
+			total_area: countries.iter().map(|c| c.area as f64).filter(|area| !area.is_nan()).sum(),
+			independent: countries.iter().filter(|c| c.independent).count(),
+			un_members: countries.iter().filter(|c| c.un_member).count(),
+		}
+	}
+
+	/// People per km² across the whole group, so large countries weigh more than small ones.
+	pub fn density(&self) -> f64 {
+		if self.total_area > 0.0 {
+			self.total_population as f64 / self.total_area
+		} else {
+			0.0
+		}
+	}
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct RegionStats {
+	pub region: Region,
+	pub stats: GroupStats,
+	/// Sorted by name.
+	pub subregions: Vec<GroupStats>,
+}
+
+/// Aggregates countries by region in display order, skipping regions without any countries.
+pub fn region_stats(countries: &[CountryOverview]) -> Vec<RegionStats> {
+	Region::iter()
+		.filter_map(|region| {
+			let in_region = countries.iter().filter(|c| c.region == region).collect::<Vec<_>>();
+			if in_region.is_empty() {
+				return None;
+			}
+
+			let mut names = in_region
+				.iter()
+				.map(|c| c.sub_region.as_str())
+				.filter(|s| !s.is_empty())
+				.collect::<Vec<_>>();
+			names.sort_unstable();
+			names.dedup();
+
+			let subregions = names
+				.into_iter()
+				.map(|name| {
+					let in_subregion = in_region.iter().copied().filter(|c| c.sub_region == name).collect::<Vec<_>>();
+					GroupStats::new(name, &in_subregion)
+				})
+				.collect();
+
+			Some(RegionStats {
+				region,
+				stats: GroupStats::new(&region.to_string(), &in_region),
+				subregions,
+			})
+		})
+		.collect()
+}
+
+/// Figure the region chart is drawn for.
+#[derive(EnumIter, Display, EnumString, PartialEq, Default, Debug, Copy, Clone)]
+pub enum StatsMetric {
+	#[default]
+	#[strum(to_string = "Total population")]
+	TotalPopulation,
+	#[strum(to_string = "Median population")]
+	MedianPopulation,
+	#[strum(to_string = "Total area")]
+	TotalArea,
+	#[strum(to_string = "Average density")]
+	Density,
+	#[strum(to_string = "Countries")]
+	Count,
+}
+
+impl StatsMetric {
+	pub fn value(self, stats: &GroupStats) -> f64 {
+		match self {
+			StatsMetric::TotalPopulation => stats.total_population as f64,
+			StatsMetric::MedianPopulation => stats.median_population,
+			StatsMetric::TotalArea => stats.total_area,
+			StatsMetric::Density => stats.density(),
+			StatsMetric::Count => stats.count as f64,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::str::FromStr;
+	use crate::types::{Flags, Name, CCA3};
+	use super::*;
+
+	fn country(cca3: &str, region: Region, sub_region: &str, population: u32, area: f32) -> CountryOverview {
+		CountryOverview {
+			name: Name {
+				common: cca3.to_string(),
+				official: cca3.to_string(),
+				native_name: Default::default()
+			},
+			cca3: CCA3::from_str(cca3).unwrap(),
+			cca2: cca3[..2].to_string(),
+			alt_spellings: Vec::new(),
+			independent: true,
+			un_member: cca3 != "XKX",
+			region,
+			sub_region: sub_region.to_string(),
+			area,
+			population,
+			flags: Flags {
+				png: String::new(),
+				svg: String::new(),
+				alt: None
+			},
+			borders: None
+		}
+	}
+
+	fn stats(countries: &[CountryOverview]) -> GroupStats {
+		GroupStats::new("Group", &countries.iter().collect::<Vec<_>>())
+	}
+
+	#[test]
+	fn median_of_an_odd_count_is_the_middle_value() {
+		let countries = [
+			country("AAA", Region::Europe, "", 300, 1.0),
+			country("BBB", Region::Europe, "", 10, 1.0),
+			country("CCC", Region::Europe, "", 2_000, 1.0),
+		];
+		assert_eq!(stats(&countries).median_population, 300.0);
+	}
+
+	#[test]
+	fn median_of_an_even_count_averages_the_middle_values() {
+		let countries = [
+			country("AAA", Region::Europe, "", 300, 1.0),
+			country("BBB", Region::Europe, "", 10, 1.0),
+			country("CCC", Region::Europe, "", 2_000, 1.0),
+			country("DDD", Region::Europe, "", 0, 1.0),
+		];
+		assert_eq!(stats(&countries).median_population, 155.0);
+		// Past u32::MAX when added as integers
+		let large = [
+			country("AAA", Region::Asia, "", u32::MAX, 1.0),
+			country("BBB", Region::Asia, "", u32::MAX - 1, 1.0),
+		];
+		assert_eq!(stats(&large).median_population, u32::MAX as f64 - 0.5);
+	}
+
+	#[test]
+	fn an_empty_group_has_zero_figures() {
+		let empty = stats(&[]);
+		assert_eq!(empty.count, 0);
+		assert_eq!(empty.median_population, 0.0);
+		assert_eq!(empty.density(), 0.0);
+	}
+
+	#[test]
+	fn density_is_total_population_over_total_area() {
+		let countries = [
+			country("AAA", Region::Europe, "", 1_000, 10.0),
+			country("BBB", Region::Europe, "", 3_000, 30.0),
+			// Counted in the population but not the area
+			country("CCC", Region::Europe, "", 1_000, f32::NAN),
+		];
+		let group = stats(&countries);
+
+		assert_eq!(group.total_population, 5_000);
+		assert_eq!(group.total_area, 40.0);
+		assert_eq!(group.density(), 125.0);
+		assert_eq!(StatsMetric::Density.value(&group), 125.0);
+
+		let uninhabited = stats(&[country("AAA", Region::Antarctic, "", 0, f32::NAN)]);
+		assert_eq!(uninhabited.total_area, 0.0);
+		assert_eq!(uninhabited.density(), 0.0);
+	}
+
+	#[test]
+	fn groups_regions_and_their_subregions() {
+		let countries = [
+			country("DEU", Region::Europe, "Western Europe", 80, 1.0),
+			country("POL", Region::Europe, "Central Europe", 40, 1.0),
+			country("XKX", Region::Europe, "", 2, 1.0),
+			country("JPN", Region::Asia, "Eastern Asia", 120, 1.0),
+		];
+		let regions = region_stats(&countries);
+
+		assert_eq!(regions.iter().map(|r| r.region).collect::<Vec<_>>(), [Region::Asia, Region::Europe]);
+		let europe = &regions[1];
+		assert_eq!(europe.stats.count, 3);
+		assert_eq!(europe.stats.un_members, 2);
+		assert_eq!(europe.subregions.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(), ["Central Europe", "Western Europe"]);
+	}
+}
@@ -0,0 +1,3 @@
+mod aggregate;
+
+pub use aggregate::*;
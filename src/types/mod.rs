@@ -1,7 +0,0 @@
-mod cca3;
-mod countries_api;
-mod filters;
-
-pub use cca3::*;
-pub use countries_api::*;
-pub use filters::*;
@@ -0,0 +1,25 @@
+//! Country types, search and list queries behind WorldRanks, with no UI framework or browser
+//! dependency so they run anywhere `std` does.
+//!
+//! A [`ListQuery`](types::ListQuery) holds the same filters and sort as the country list URL and
+//! applies them to any list of [`CountryOverview`](types::CountryOverview)s:
+//!
+//! ```
+//! use world_ranks_core::types::{ListQuery, Region};
+//!
+//! let query = ListQuery::from("region=Europe&sort=-Area&un=1");
+//! assert_eq!(query.regions, vec![Region::Europe]);
+//!
+//! let countries = Vec::new();
+//! assert!(query.apply(&countries).is_empty());
+//! ```
+//!
+//! The building blocks are public too: [`filter_data`] with a list of
+//! [`FilterQuery`](types::FilterQuery)s, [`sort_data`] with [`SortKey`](types::SortKey)s, and
+//! [`toggle_region`] and [`toggle_subregion`] for filter controls.
+
+pub mod query;
+pub mod search;
+pub mod types;
+
+pub use query::*;
@@ -0,0 +1,78 @@
+use std::cmp::Ordering;
+use crate::{
+	search::ParsedQuery,
+	types::{CountryOverview, FilterQuery, ListQuery, Region, SortKey}
+};
+
+/// Countries matching every query, in their original order. An empty list of regions,
+/// subregions or statuses matches every country, and subregions match regardless of case.
+pub fn filter_data(countries: &[CountryOverview], queries: &[FilterQuery]) -> Vec<CountryOverview> {
+	countries
+		.iter()
+		.filter(|c| {
+			queries.iter().all(|query| match query {
+				FilterQuery::Text(search_query) => search_query.score(c).is_some(),
+				FilterQuery::Region(region_query) => region_query.is_empty() || region_query.contains(&c.region),
+				FilterQuery::Subregion(subregion_query) => {
+					subregion_query.is_empty() || subregion_query.iter().any(|s| s.eq_ignore_ascii_case(&c.sub_region))
+				}
+				FilterQuery::Status(status_query) => {
+					status_query.iter().all(|(status, filter)| filter.matches(status.of(c)))
+				}
+				FilterQuery::PopulationRange(range) => range.contains(c.population as f64),
+				FilterQuery::AreaRange(range) => range.contains(c.area as f64),
+			})
+		})
+		.cloned()
+		.collect()
+}
+
+/// Sorts on each key in turn, later keys breaking ties left by earlier ones. The sort is
+/// stable, so countries equal on every key keep their order.
+pub fn sort_data(mut countries: Vec<CountryOverview>, sort: &[SortKey]) -> Vec<CountryOverview> {
+	countries.sort_by(|a, b| {
+		sort.iter().fold(Ordering::Equal, |ordering, key| ordering.then_with(|| key.compare(a, b)))
+	});
+	countries
+}
+
+/// Adds `region` to the filter, or removes it when already there.
+pub fn toggle_region(regions: &mut Vec<Region>, region: Region) {
+	if regions.contains(&region) {
+		regions.retain(|r| r != &region);
+	} else {
+		regions.push(region);
+	}
+}
+
+/// Adds `subregion` to the filter, or removes it when already there in any case, as the
+/// filter itself ignores case.
+pub fn toggle_subregion(subregions: &mut Vec<String>, subregion: &str) {
+	if subregions.iter().any(|s| s.eq_ignore_ascii_case(subregion)) {
+		subregions.retain(|s| !s.eq_ignore_ascii_case(subregion));
+	} else {
+		subregions.push(subregion.to_string());
+	}
+}
+
+impl ListQuery {
+	/// The countries matching every filter of the query, sorted by its keys with the most relevant
+	/// search results first. Filters typed into the search text, see [`ParsedQuery`], narrow the
+	/// others down and a `sort:` there replaces the query's sort. Paging is left to the caller.
+	pub fn apply(&self, countries: &[CountryOverview]) -> Vec<CountryOverview> {
+		let search = ParsedQuery::parse(&self.search);
+		let sort = search.sort_or(&self.sort);
+
+		let mut queries = search.filters();
+		queries.extend([
+			FilterQuery::Region(&self.regions),
+			FilterQuery::Subregion(&self.subregions),
+			FilterQuery::Status(&self.statuses),
+			FilterQuery::PopulationRange(self.population),
+			FilterQuery::AreaRange(self.area)
+		]);
+
+		// The sort breaks ties between equally relevant results
+		search.text.rank(filter_data(&sort_data(countries.to_vec(), sort), &queries))
+	}
+}
@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+use crate::{
+	search::SearchQuery,
+	types::{FilterQuery, Region, SortBy, SortDirection, SortKey, Status, StatusFilter, ValueRange}
+};
+
+/// Filters typed into the search box next to plain text, e.g.
+/// `region:europe pop>10m area<50k un:yes sort:-density "united kingdom"`.
+///
+/// - `region:` and `subregion:` take one name or several separated by commas.
+/// - `pop` and `area` compare with `>`, `>=`, `<`, `<=` or `=`, or take a `min-max` range after
+///   `:`. Numbers accept `k`, `m` and `b` suffixes.
+/// - `un:` and `independent:` take `yes` or `no`.
+/// - `sort:` takes sort keys separated by commas, with `-` for descending.
+///
+/// Double quotes keep spaces inside a value or a phrase. Every other word is matched as text.
+#[derive(Clone, PartialEq, Default)]
+pub struct ParsedQuery {
+	/// The words outside any filter.
+	pub text: SearchQuery,
+	pub regions: Vec<Region>,
+	pub subregions: Vec<String>,
+	pub statuses: HashMap<Status, StatusFilter>,
+	pub population: ValueRange,
+	pub area: ValueRange,
+	/// Empty unless the query has a `sort:` filter, which then replaces the chosen sort.
+	pub sort: Vec<SortKey>,
+	/// Tokens that could not be understood. They are left out of both the filters and the text.
+	pub errors: Vec<QueryError>,
+}
+
+/// A token of the query that could not be understood, and why.
+#[derive(Clone, PartialEq, Debug)]
+pub struct QueryError {
+	/// Byte range of the token in the query.
+	pub span: Range<usize>,
+	pub token: String,
+	pub message: String,
+}
+
+impl fmt::Display for QueryError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}: {}", self.token, self.message)
+	}
+}
+
+impl std::error::Error for QueryError {}
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+enum Operator {
+	Colon,
+	Equal,
+	Greater,
+	GreaterOrEqual,
+	Less,
+	LessOrEqual,
+}
+
+const OPERATORS: [(&str, Operator); 6] = [
+	(">=", Operator::GreaterOrEqual),
+	("<=", Operator::LessOrEqual),
+	(":", Operator::Colon),
+	("=", Operator::Equal),
+	(">", Operator::Greater),
+	("<", Operator::Less),
+];
+
+const FILTER_KEYS: [&str; 11] = [
+	"region", "regions", "subregion", "subregions", "sub", "un", "independent", "pop", "population", "area", "sort",
+];
+const FILTER_NAMES: &str = "region, subregion, pop, area, un, independent or sort";
+
+struct Token<'a> {
+	raw: &'a str,
+	span: Range<usize>,
+}
+
+impl ParsedQuery {
+	pub fn parse(input: &str) -> Self {
+		let mut parsed = ParsedQuery::default();
+		let mut words = Vec::new();
+
+		for token in tokenize(input) {
+			let result = if token.raw.matches('"').count() % 2 == 1 {
+				Err("Missing the closing quote".to_string())
+			} else if let Some((key, operator, value)) = split_filter(token.raw) {
+				parsed.apply(&key.to_lowercase(), operator, &unquote(value))
+			} else {
+				words.push(unquote(token.raw));
+				Ok(())
+			};
+
+			if let Err(message) = result {
+				parsed.errors.push(QueryError {
+					token: token.raw.to_string(),
+					span: token.span,
+					message
+				});
+			}
+		}
+
+		parsed.text = SearchQuery::new(&words.join(" "));
+		parsed
+	}
+
+	pub fn is_valid(&self) -> bool {
+		self.errors.is_empty()
+	}
+
+	/// The filters of the query apart from its text, to combine with any others in
+	/// [`filter_data`](crate::filter_data). The text is matched by [`SearchQuery::rank`].
+	pub fn filters(&self) -> Vec<FilterQuery<'_>> {
+		vec![
+			FilterQuery::Region(&self.regions),
+			FilterQuery::Subregion(&self.subregions),
+			FilterQuery::Status(&self.statuses),
+			FilterQuery::PopulationRange(self.population),
+			FilterQuery::AreaRange(self.area),
+		]
+	}
+
+	/// The `sort:` keys of the query, or `sort` when it has none.
+	pub fn sort_or<'a>(&'a self, sort: &'a [SortKey]) -> &'a [SortKey] {
+		match self.sort.is_empty() {
+			true => sort,
+			false => &self.sort,
+		}
+	}
+
+	fn apply(&mut self, key: &str, operator: Operator, value: &str) -> Result<(), String> {
+		let list = || value.split(',').map(str::trim).filter(|item| !item.is_empty());
+		let colon = || match operator {
+			Operator::Colon => Ok(()),
+			_ => Err(format!("Use {key}:{value}")),
+		};
+
+		if !FILTER_KEYS.contains(&key) {
+			return Err(format!("Unknown filter \"{key}\". Try {FILTER_NAMES}"));
+		}
+		if value.is_empty() {
+			return Err(format!("Missing a value after {key}"));
+		}
+
+		match key {
+			"region" | "regions" => {
+				colon()?;
+				for name in list() {
+					let region = Region::from_str(name).map_err(|_| {
+						format!("Unknown region \"{name}\". Try Africa, Americas, Antarctic, Asia, Europe or Oceania")
+					})?;
+					if !self.regions.contains(&region) {
+						self.regions.push(region);
+					}
+				}
+			},
+			"subregion" | "subregions" | "sub" => {
+				colon()?;
+				self.subregions.extend(list().map(str::to_string));
+			},
+			"un" | "independent" => {
+				colon()?;
+				let status = if key == "un" { Status::UN } else { Status::Independent };
+				let filter = match value.to_lowercase().as_str() {
+					"yes" | "y" | "true" | "1" => StatusFilter::Yes,
+					"no" | "n" | "false" | "0" => StatusFilter::No,
+					_ => return Err("Expected yes or no".to_string()),
+				};
+				self.statuses.insert(status, filter);
+			},
+			"pop" | "population" => self.population = narrow(self.population, operator, value)?,
+			"area" => self.area = narrow(self.area, operator, value)?,
+			"sort" => {
+				colon()?;
+				for key in list() {
+					self.sort.push(sort_key(key)?);
+				}
+			},
+			_ => unreachable!("every key in FILTER_KEYS is handled"),
+		}
+		Ok(())
+	}
+}
+
+/// Splits the query on whitespace outside double quotes.
+fn tokenize(input: &str) -> Vec<Token<'_>> {
+	let mut tokens = Vec::new();
+	let mut start = None;
+	let mut quoted = false;
+
+	for (i, c) in input.char_indices() {
+		if c == '"' {
+			quoted = !quoted;
+		}
+		match (start, c.is_whitespace() && !quoted) {
+			(None, false) => start = Some(i),
+			(Some(from), true) => {
+				tokens.push(Token { raw: &input[from..i], span: from..i });
+				start = None;
+			},
+			_ => {}
+		}
+	}
+	if let Some(from) = start {
+		tokens.push(Token { raw: &input[from..], span: from..input.len() });
+	}
+
+	tokens
+}
+
+/// `key`, operator and value of a filter token. Tokens starting with a quote or without an
+/// operator before any quote are text.
+fn split_filter(token: &str) -> Option<(&str, Operator, &str)> {
+	let unquoted = &token[..token.find('"').unwrap_or(token.len())];
+	let (at, symbol, operator) = OPERATORS
+		.iter()
+		.filter_map(|&(symbol, operator)| Some((unquoted.find(symbol)?, symbol, operator)))
+		// Earliest operator wins, and among operators at the same place the longest
+		.min_by_key(|&(at, symbol, _)| (at, std::cmp::Reverse(symbol.len())))?;
+
+	let key = &token[..at];
+	if key.is_empty() || !key.chars().all(char::is_alphabetic) {
+		return None;
+	}
+	Some((key, operator, &token[at + symbol.len()..]))
+}
+
+fn unquote(text: &str) -> String {
+	text.replace('"', "")
+}
+
+/// Adds a comparison to a range, keeping the bounds already set by other tokens.
+fn narrow(range: ValueRange, operator: Operator, value: &str) -> Result<ValueRange, String> {
+	let (min, max) = match operator {
+		Operator::Colon => {
+			let (min, max) = value.split_once('-').ok_or("Expected a range such as 1m-10m, 1m- or -10m")?;
+			(optional_amount(min)?, optional_amount(max)?)
+		},
+		Operator::Equal => {
+			let value = amount(value)?;
+			(Some(value), Some(value))
+		},
+		// Ranges are inclusive, so strict comparisons move to the next representable number
+		Operator::Greater => (Some(amount(value)?.next_up()), None),
+		Operator::GreaterOrEqual => (Some(amount(value)?), None),
+		Operator::Less => (None, Some(amount(value)?.next_down())),
+		Operator::LessOrEqual => (None, Some(amount(value)?)),
+	};
+
+	Ok(ValueRange {
+		min: [range.min, min].into_iter().flatten().reduce(f64::max),
+		max: [range.max, max].into_iter().flatten().reduce(f64::min)
+	})
+}
+
+fn optional_amount(value: &str) -> Result<Option<f64>, String> {
+	match value.trim() {
+		"" => Ok(None),
+		value => amount(value).map(Some),
+	}
+}
+
+/// A number such as `1500`, `2.5k`, `10m` or `1b`. Commas and underscores between digits are ignored.
+fn amount(value: &str) -> Result<f64, String> {
+	let invalid = || format!("Expected a number such as 50k, 10m or 1.5b instead of \"{value}\"");
+	let digits = value.replace([',', '_'], "");
+	let (number, multiplier) = match digits.char_indices().last() {
+		Some((i, 'k' | 'K')) => (&digits[..i], 1e3),
+		Some((i, 'm' | 'M')) => (&digits[..i], 1e6),
+		Some((i, 'b' | 'B')) => (&digits[..i], 1e9),
+		_ => (digits.as_str(), 1.0),
+	};
+
+	match number.parse::<f64>() {
+		Ok(number) if number.is_finite() && number >= 0.0 => Ok(number * multiplier),
+		_ => Err(invalid()),
+	}
+}
+
+fn sort_key(key: &str) -> Result<SortKey, String> {
+	let (name, descending) = match key.strip_prefix('-') {
+		Some(name) => (name, true),
+		None => (key.strip_prefix('+').unwrap_or(key), false),
+	};
+	let by = match name.to_lowercase().as_str() {
+		"pop" => SortBy::Population,
+		name => SortBy::from_str(name).map_err(|_| {
+			format!("Unknown sort \"{name}\". Try name, population, area, density, region, subregion or neighbours")
+		})?,
+	};
+
+	Ok(SortKey {
+		by,
+		direction: match descending {
+			true => SortDirection::Descending,
+			false => SortDirection::Ascending,
+		}
+	})
+}
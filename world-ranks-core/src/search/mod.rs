@@ -0,0 +1,7 @@
+mod language;
+mod normalize;
+mod query;
+
+pub use language::*;
+pub use normalize::*;
+pub use query::*;
@@ -0,0 +1,28 @@
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+/// Folds text for comparison: lowercase, diacritics removed and punctuation turned into
+/// single spaces, so "Côte d'Ivoire" becomes "cote d ivoire".
+pub fn normalize(text: &str) -> String {
+	let mut normalized = String::with_capacity(text.len());
+
+	for c in text.nfkd().filter(|c| !is_combining_mark(*c)).flat_map(char::to_lowercase) {
+		match c {
+			// Letters that have no decomposition into a base letter and a mark
+			'ß' => normalized.push_str("ss"),
+			'æ' => normalized.push_str("ae"),
+			'œ' => normalized.push_str("oe"),
+			'þ' => normalized.push_str("th"),
+			'ø' => normalized.push('o'),
+			'ł' => normalized.push('l'),
+			'đ' | 'ð' => normalized.push('d'),
+			'ı' => normalized.push('i'),
+			c if c.is_alphanumeric() => normalized.push(c),
+			_ => if !normalized.is_empty() && !normalized.ends_with(' ') {
+				normalized.push(' ');
+			}
+		}
+	}
+
+	normalized.truncate(normalized.trim_end().len());
+	normalized
+}
@@ -0,0 +1,118 @@
+use strsim::damerau_levenshtein;
+use crate::{search::normalize, types::CountryOverview};
+
+/// Results scoring below this are not considered a match.
+const MIN_SCORE: f32 = 0.5;
+/// Region and subregion matches rank below any name containing the query, while a place
+/// merely containing it still clears [`MIN_SCORE`].
+const PLACE_WEIGHT: f32 = 0.75;
+/// Typo-tolerant matches rank below any exact substring match.
+const FUZZY_WEIGHT: f32 = 0.7;
+
+/// A normalized search string that scores countries by relevance.
+#[derive(Clone, PartialEq, Default)]
+pub struct SearchQuery {
+	text: String,
+	tokens: Vec<String>,
+}
+
+impl SearchQuery {
+	pub fn new(text: &str) -> Self {
+		let text = normalize(text);
+		let tokens = text.split(' ').filter(|t| !t.is_empty()).map(str::to_string).collect();
+		SearchQuery { text, tokens }
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.text.is_empty()
+	}
+
+	/// Relevance of a country between 0 and 1, or `None` when it does not match. Every
+	/// country matches an empty query.
+	pub fn score(&self, country: &CountryOverview) -> Option<f32> {
+		if self.is_empty() {
+			return Some(0.0);
+		}
+
+		if [country.cca3.as_str(), &country.cca2].iter().any(|code| normalize(code) == self.text) {
+			return Some(1.0);
+		}
+
+		let names = [&country.name.common, &country.name.official]
+			.into_iter()
+			.chain(country.name.native_name.values().flat_map(|n| [&n.common, &n.official]))
+			.chain(country.alt_spellings.iter())
+			.map(|name| self.score_text(&normalize(name)));
+		let places = [country.region.to_string(), country.sub_region.clone()]
+			.into_iter()
+			.map(|place| PLACE_WEIGHT * self.score_text(&normalize(&place)));
+
+		names
+			.chain(places)
+			.max_by(f32::total_cmp)
+			.filter(|score| *score >= MIN_SCORE)
+	}
+
+	/// Keeps the countries matching the query, most relevant first, scoring each only once.
+	/// The sort is stable, so countries that score the same keep their current order.
+	pub fn rank(&self, countries: Vec<CountryOverview>) -> Vec<CountryOverview> {
+		if self.is_empty() {
+			return countries;
+		}
+
+		let mut scored = countries
+			.into_iter()
+			.filter_map(|country| Some((self.score(&country)?, country)))
+			.collect::<Vec<_>>();
+		scored.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+		scored.into_iter().map(|(_, country)| country).collect()
+	}
+
+	fn score_text(&self, text: &str) -> f32 {
+		if text == self.text {
+			1.0
+		} else if text.starts_with(&self.text) {
+			0.95
+		} else if text.contains(&format!(" {}", self.text)) {
+			0.9
+		} else if text.contains(&self.text) {
+			0.8
+		} else {
+			FUZZY_WEIGHT * self.fuzzy_score(text)
+		}
+	}
+
+	/// Averages how closely each query token matches its best word in `text`, allowing one
+	/// typo in words of four letters or more and two from eight. Any token without a close
+	/// enough word makes the whole text a miss.
+	fn fuzzy_score(&self, text: &str) -> f32 {
+		let words = text.split(' ').collect::<Vec<_>>();
+		let mut total = 0.0;
+
+		for token in &self.tokens {
+			let len = token.chars().count();
+			let allowed = match len {
+				0..=3 => 0,
+				4..=7 => 1,
+				_ => 2,
+			};
+
+			let best = words
+				.iter()
+				.map(|word| {
+					// Compare against the start of longer words so partially typed names still match
+					let prefix = word.chars().take(len).collect::<String>();
+					damerau_levenshtein(token, word).min(damerau_levenshtein(token, &prefix))
+				})
+				.min()
+				.filter(|distance| *distance <= allowed);
+
+			match best {
+				Some(distance) => total += 1.0 - distance as f32 / len as f32,
+				None => return 0.0,
+			}
+		}
+
+		total / self.tokens.len() as f32
+	}
+}
@@ -1,8 +1,8 @@
 use std::fmt;
 use std::str::FromStr;
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct CCA3([u8; 3]);
 
 #[derive(Debug)]
@@ -33,6 +33,15 @@ impl<'de> Deserialize<'de> for CCA3 {
 	}
 }
 
+impl Serialize for CCA3 {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_str(self.as_str())
+	}
+}
+
 impl std::error::Error for InvalidCCA3 {}
 
 impl FromStr for CCA3 {
@@ -53,4 +62,31 @@ impl CCA3 {
 	pub fn as_str(&self) -> &str {
 		std::str::from_utf8(&self.0).unwrap()
 	}
-}
\ No newline at end of file
+}
+
+/// Comma separated country codes, as used in `/compare/DEU,FRA,POL`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CCA3List(pub Vec<CCA3>);
+
+impl fmt::Display for CCA3List {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let codes = self.0
+			.iter()
+			.map(CCA3::as_str)
+			.collect::<Vec<_>>()
+			.join(",");
+		write!(f, "{codes}")
+	}
+}
+
+impl FromStr for CCA3List {
+	type Err = InvalidCCA3;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		s.split(',')
+			.filter(|code| !code.is_empty())
+			.map(CCA3::from_str)
+			.collect::<Result<Vec<_>, _>>()
+			.map(CCA3List)
+	}
+}
@@ -0,0 +1,257 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::types::{CCA3, Region};
+
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
+pub struct NativeName {
+	pub common: String,
+	pub official: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
+pub struct Name {
+	pub common: String,
+	pub official: String,
+	/// Keyed by ISO 639-3 language code.
+	#[serde(rename = "nativeName", default)]
+	pub native_name: HashMap<String, NativeName>,
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
+pub struct Currency {
+	pub symbol: String,
+	pub name: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
+pub struct Flags {
+	pub png: String,
+	pub svg: String,
+	pub alt: Option<String>,
+}
+
+/// International dialling prefix, e.g. root `+4` with suffix `9` for Germany.
+#[derive(Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct Idd {
+	pub root: Option<String>,
+	#[serde(default)]
+	pub suffixes: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct Car {
+	/// International vehicle registration codes.
+	#[serde(default)]
+	pub signs: Vec<String>,
+	/// `left` or `right`.
+	pub side: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
+pub struct Demonym {
+	#[serde(rename = "f")]
+	pub female: String,
+	#[serde(rename = "m")]
+	pub male: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
+pub struct PostalCode {
+	pub format: String,
+	pub regex: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct CoatOfArms {
+	pub png: Option<String>,
+	pub svg: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct Maps {
+	#[serde(rename = "googleMaps")]
+	pub google_maps: Option<String>,
+	#[serde(rename = "openStreetMaps")]
+	pub open_street_maps: Option<String>,
+}
+
+/// A full `/alpha/{cca3}` record. Fields beyond the basics default to empty, so records
+/// from the offline snapshot or an older cache still deserialize.
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
+pub struct Country {
+	pub name: Name,
+	pub cca3: CCA3,
+	pub currencies: Option<HashMap<String, Currency>>,
+	pub capital: Option<Vec<String>>,
+	pub region: String,
+	#[serde(rename = "subregion")]
+	pub sub_region: Option<String>,
+	pub languages: Option<HashMap<String, String>>,
+	pub borders: Option<Vec<CCA3>>,
+	pub area: f32,
+	pub population: u32,
+	pub flags: Flags,
+	#[serde(default)]
+	pub cca2: String,
+	pub ccn3: Option<String>,
+	pub cioc: Option<String>,
+	#[serde(default)]
+	pub tld: Vec<String>,
+	#[serde(default)]
+	pub idd: Idd,
+	#[serde(default)]
+	pub timezones: Vec<String>,
+	#[serde(default)]
+	pub continents: Vec<String>,
+	#[serde(default)]
+	pub latlng: Vec<f64>,
+	pub landlocked: Option<bool>,
+	#[serde(default)]
+	pub car: Car,
+	/// Keyed by language code, e.g. `eng` and `fra`.
+	#[serde(default)]
+	pub demonyms: HashMap<String, Demonym>,
+	/// Keyed by the year of the measurement.
+	#[serde(default)]
+	pub gini: HashMap<String, f32>,
+	pub fifa: Option<String>,
+	#[serde(rename = "startOfWeek")]
+	pub start_of_week: Option<String>,
+	#[serde(rename = "postalCode")]
+	pub postal_code: Option<PostalCode>,
+	#[serde(rename = "coatOfArms", default)]
+	pub coat_of_arms: CoatOfArms,
+	#[serde(default)]
+	pub maps: Maps,
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
+pub struct CountryOverview {
+	pub name: Name,
+	pub cca3: CCA3,
+	#[serde(default)]
+	pub cca2: String,
+	#[serde(rename = "altSpellings", default)]
+	pub alt_spellings: Vec<String>,
+	pub independent: bool,
+	#[serde(rename = "unMember")]
+	pub un_member: bool,
+	pub region: Region,
+	#[serde(rename = "subregion")]
+	pub sub_region: String,
+	pub area: f32,
+	pub population: u32,
+	pub flags: Flags,
+	pub borders: Option<Vec<CCA3>>,
+}
+
+/// People per km², NaN when the area is missing or zero.
+fn density(population: u32, area: f32) -> f32 {
+	if area > 0.0 {
+		population as f32 / area
+	} else {
+		f32::NAN
+	}
+}
+
+impl Country {
+	pub fn density(&self) -> f32 {
+		density(self.population, self.area)
+	}
+
+	/// Full dialling codes such as `+49`. Countries sharing a root across many area codes,
+	/// like the `+1` of the United States, are shown by their root alone.
+	pub fn calling_codes(&self) -> Vec<String> {
+		let Some(root) = self.idd.root.as_ref() else {
+			return Vec::new();
+		};
+
+		if (1..=3).contains(&self.idd.suffixes.len()) {
+			self.idd.suffixes.iter().map(|suffix| format!("{root}{suffix}")).collect()
+		} else {
+			vec![root.clone()]
+		}
+	}
+
+	/// English demonym, falling back to the language with the lowest code so the choice
+	/// doesn't depend on the order of the map.
+	pub fn demonym(&self) -> Option<&Demonym> {
+		self.demonyms.get("eng").or_else(|| {
+			self.demonyms
+				.iter()
+				.min_by_key(|(language, _)| language.as_str())
+				.map(|(_, demonym)| demonym)
+		})
+	}
+
+	/// The most recent Gini coefficient and the year it was measured.
+	pub fn latest_gini(&self) -> Option<(&str, f32)> {
+		self.gini
+			.iter()
+			.max_by(|(a, _), (b, _)| a.cmp(b))
+			.map(|(year, gini)| (year.as_str(), *gini))
+	}
+}
+
+impl CountryOverview {
+	pub fn density(&self) -> f32 {
+		density(self.population, self.area)
+	}
+
+	pub fn neighbour_count(&self) -> usize {
+		self.borders.as_ref().map_or(0, Vec::len)
+	}
+}
+
+/// Search-only fields fetched separately from the overview, since `/all` accepts at most ten fields.
+#[derive(Deserialize, Clone, PartialEq)]
+pub struct SearchFields {
+	pub cca3: CCA3,
+	pub cca2: String,
+	#[serde(rename = "altSpellings")]
+	pub alt_spellings: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
+pub struct NeighbouringCountry {
+	pub name: Name,
+	pub flags: Flags,
+	pub cca3: CCA3,
+}
+
+impl From<CountryOverview> for Country {
+	/// Builds a partial record for rendering before the full country has loaded. Fields the
+	/// overview does not carry are left as `None`.
+	fn from(overview: CountryOverview) -> Self {
+		Country {
+			name: overview.name,
+			cca3: overview.cca3,
+			currencies: None,
+			capital: None,
+			region: overview.region.to_string(),
+			sub_region: Some(overview.sub_region).filter(|s| !s.is_empty()),
+			languages: None,
+			borders: overview.borders,
+			area: overview.area,
+			population: overview.population,
+			flags: overview.flags,
+			cca2: overview.cca2,
+			ccn3: None,
+			cioc: None,
+			tld: Vec::new(),
+			idd: Idd::default(),
+			timezones: Vec::new(),
+			continents: Vec::new(),
+			latlng: Vec::new(),
+			landlocked: None,
+			car: Car::default(),
+			demonyms: HashMap::new(),
+			gini: HashMap::new(),
+			fifa: None,
+			start_of_week: None,
+			postal_code: None,
+			coat_of_arms: CoatOfArms::default(),
+			maps: Maps::default(),
+		}
+	}
+}
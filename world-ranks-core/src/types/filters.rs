@@ -0,0 +1,282 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use strum_macros::{Display, EnumIter, EnumString};
+use crate::{search::SearchQuery, types::CountryOverview};
+
+#[derive(EnumIter, Display, EnumString, PartialEq, Default, Debug, Copy, Clone)]
+#[strum(ascii_case_insensitive)]
+pub enum SortBy {
+	Name,
+	#[default]
+	Population,
+	Area,
+	Density,
+	Region,
+	Subregion,
+	Neighbours,
+}
+
+impl SortBy {
+	/// Text sorts A to Z first, numbers largest first.
+	pub fn default_direction(&self) -> SortDirection {
+		match self {
+			SortBy::Name | SortBy::Region | SortBy::Subregion => SortDirection::Ascending,
+			SortBy::Population | SortBy::Area | SortBy::Density | SortBy::Neighbours => SortDirection::Descending,
+		}
+	}
+
+	/// Ascending comparison of two countries on this key. Floats use a total order, so
+	/// NaN areas and densities compare above every number instead of panicking. Sorts go
+	/// through [`SortKey::compare`], which moves them last in either direction.
+	pub fn compare(&self, a: &CountryOverview, b: &CountryOverview) -> Ordering {
+		match self {
+			SortBy::Name => a.name.common.cmp(&b.name.common),
+			SortBy::Population => a.population.cmp(&b.population),
+			SortBy::Area => a.area.total_cmp(&b.area),
+			SortBy::Density => a.density().total_cmp(&b.density()),
+			SortBy::Region => a.region.to_string().cmp(&b.region.to_string()),
+			SortBy::Subregion => a.sub_region.cmp(&b.sub_region),
+			SortBy::Neighbours => a.neighbour_count().cmp(&b.neighbour_count()),
+		}
+	}
+
+	/// Whether the country has no value for this key, like the NaN density of a country with no area.
+	fn is_missing(&self, country: &CountryOverview) -> bool {
+		match self {
+			SortBy::Area => country.area.is_nan(),
+			SortBy::Density => country.density().is_nan(),
+			_ => false,
+		}
+	}
+}
+
+#[derive(EnumIter, Display, PartialEq, Debug, Copy, Clone)]
+pub enum SortDirection {
+	Ascending,
+	Descending,
+}
+
+impl SortDirection {
+	pub fn toggle(&self) -> Self {
+		match self {
+			SortDirection::Ascending => SortDirection::Descending,
+			SortDirection::Descending => SortDirection::Ascending,
+		}
+	}
+
+	pub fn apply(&self, ordering: Ordering) -> Ordering {
+		match self {
+			SortDirection::Ascending => ordering,
+			SortDirection::Descending => ordering.reverse(),
+		}
+	}
+
+	pub fn indicator(&self) -> &'static str {
+		match self {
+			SortDirection::Ascending => "▲",
+			SortDirection::Descending => "▼",
+		}
+	}
+}
+
+/// One key of a multi-key sort. Written as `Population` or `-Population` for descending.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct SortKey {
+	pub by: SortBy,
+	pub direction: SortDirection,
+}
+
+impl SortKey {
+	pub fn new(by: SortBy) -> Self {
+		SortKey {
+			by,
+			direction: by.default_direction()
+		}
+	}
+
+	/// Countries missing a value for the key sort last whatever the direction, so sorting by
+	/// density never starts with the countries that have no area.
+	pub fn compare(&self, a: &CountryOverview, b: &CountryOverview) -> Ordering {
+		match (self.by.is_missing(a), self.by.is_missing(b)) {
+			(true, true) => Ordering::Equal,
+			(true, false) => Ordering::Greater,
+			(false, true) => Ordering::Less,
+			(false, false) => self.direction.apply(self.by.compare(a, b)),
+		}
+	}
+}
+
+impl Default for SortKey {
+	fn default() -> Self {
+		SortKey::new(SortBy::default())
+	}
+}
+
+impl fmt::Display for SortKey {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.direction {
+			SortDirection::Ascending => write!(f, "{}", self.by),
+			SortDirection::Descending => write!(f, "-{}", self.by),
+		}
+	}
+}
+
+impl FromStr for SortKey {
+	type Err = strum::ParseError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.strip_prefix('-') {
+			Some(by) => Ok(SortKey {
+				by: SortBy::from_str(by)?,
+				direction: SortDirection::Descending
+			}),
+			None => Ok(SortKey {
+				by: SortBy::from_str(s)?,
+				direction: SortDirection::Ascending
+			}),
+		}
+	}
+}
+
+#[derive(EnumIter, Display, EnumString, PartialEq, Debug, Copy, Clone)]
+#[strum(ascii_case_insensitive)]
+pub enum Region {
+	Americas,
+	Antarctic,
+	Africa,
+	Asia,
+	Europe,
+	Oceania
+}
+
+impl<'de> serde::Deserialize<'de> for Region {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		let s = String::deserialize(deserializer)?;
+		<Region as std::str::FromStr>::from_str(&s).map_err(serde::de::Error::custom)
+	}
+}
+
+impl serde::Serialize for Region {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serializer.serialize_str(&self.to_string())
+	}
+}
+
+#[derive(EnumIter, Display, EnumString, PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub enum Status {
+	#[strum(serialize = "un", to_string = "Member of the United Nations")]
+	UN,
+	#[strum(serialize = "independent", to_string = "Independent")]
+	Independent
+}
+
+impl Status {
+	/// Key used for the status in the list URL, e.g. `un=1`.
+	pub fn query_key(&self) -> &'static str {
+		match self {
+			Status::UN => "un",
+			Status::Independent => "independent",
+		}
+	}
+
+	pub fn of(&self, country: &CountryOverview) -> bool {
+		match self {
+			Status::UN => country.un_member,
+			Status::Independent => country.independent,
+		}
+	}
+}
+
+/// Whether countries must have a status, must not have it, or are not filtered on it.
+#[derive(EnumIter, Display, PartialEq, Eq, Default, Debug, Copy, Clone)]
+pub enum StatusFilter {
+	#[default]
+	Any,
+	Yes,
+	No,
+}
+
+impl StatusFilter {
+	/// Next state for a control that cycles Any → Yes → No.
+	pub fn cycle(&self) -> Self {
+		match self {
+			StatusFilter::Any => StatusFilter::Yes,
+			StatusFilter::Yes => StatusFilter::No,
+			StatusFilter::No => StatusFilter::Any,
+		}
+	}
+
+	pub fn matches(&self, value: bool) -> bool {
+		match self {
+			StatusFilter::Any => true,
+			StatusFilter::Yes => value,
+			StatusFilter::No => !value,
+		}
+	}
+}
+
+/// Inclusive bounds for a numeric filter, either side optional. Written as `min-max`,
+/// `min-` or `-max` in the URL.
+#[derive(PartialEq, Default, Debug, Copy, Clone)]
+pub struct ValueRange {
+	pub min: Option<f64>,
+	pub max: Option<f64>,
+}
+
+impl ValueRange {
+	pub fn is_unbounded(&self) -> bool {
+		self.min.is_none() && self.max.is_none()
+	}
+
+	pub fn contains(&self, value: f64) -> bool {
+		self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+	}
+}
+
+impl fmt::Display for ValueRange {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if let Some(min) = self.min {
+			write!(f, "{min}")?;
+		}
+		write!(f, "-")?;
+		if let Some(max) = self.max {
+			write!(f, "{max}")?;
+		}
+		Ok(())
+	}
+}
+
+impl FromStr for ValueRange {
+	type Err = std::num::ParseFloatError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (min, max) = s.split_once('-').unwrap_or((s, ""));
+		let bound = |value: &str| match value.trim() {
+			"" => Ok(None),
+			value => value.parse::<f64>().map(Some),
+		};
+		Ok(ValueRange {
+			min: bound(min)?,
+			max: bound(max)?
+		})
+	}
+}
+
+#[derive(Clone, Copy)]
+pub enum FilterQuery<'a> {
+	Text(&'a SearchQuery),
+	Region(&'a [Region]),
+	Subregion(&'a [String]),
+	/// Statuses missing from the map are not filtered on.
+	Status(&'a HashMap<Status, StatusFilter>),
+	PopulationRange(ValueRange),
+	AreaRange(ValueRange),
+}
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS, NON_ALPHANUMERIC};
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString};
+use crate::types::{Region, SortKey, Status, StatusFilter, ValueRange};
+
+/// Rows per page of the country list, written as `size=30` or `size=all`.
+#[derive(EnumIter, Display, EnumString, PartialEq, Default, Debug, Copy, Clone)]
+#[strum(ascii_case_insensitive)]
+pub enum PageSize {
+	#[default]
+	#[strum(serialize = "15")]
+	Fifteen,
+	#[strum(serialize = "30")]
+	Thirty,
+	#[strum(serialize = "60")]
+	Sixty,
+	#[strum(serialize = "All")]
+	All,
+}
+
+impl PageSize {
+	/// `None` when every row fits on one page.
+	pub fn rows(self) -> Option<usize> {
+		match self {
+			PageSize::Fifteen => Some(15),
+			PageSize::Thirty => Some(30),
+			PageSize::Sixty => Some(60),
+			PageSize::All => None,
+		}
+	}
+}
+
+/// State of the country list as stored in the URL, e.g. `?q=ger&sort=Area&region=Europe,Asia&un=1&pop=1000000-&page=2`.
+/// Values left at their default are omitted from the URL.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ListQuery {
+	pub search: String,
+	/// Primary key first, e.g. `sort=Region,-Population`.
+	pub sort: Vec<SortKey>,
+	pub regions: Vec<Region>,
+	pub subregions: Vec<String>,
+	/// Only statuses that are filtered on, written as `un=1` or `un=0`.
+	pub statuses: HashMap<Status, StatusFilter>,
+	pub population: ValueRange,
+	pub area: ValueRange,
+	/// Zero-based, shown one-based in the URL.
+	pub page: usize,
+	pub page_size: PageSize,
+	/// Load the next page when scrolling to the bottom instead of paging, written as `scroll=1`.
+	pub infinite: bool,
+}
+
+impl Default for ListQuery {
+	fn default() -> Self {
+		ListQuery {
+			search: String::new(),
+			sort: vec![SortKey::default()],
+			regions: Vec::new(),
+			subregions: Vec::new(),
+			statuses: HashMap::new(),
+			population: ValueRange::default(),
+			area: ValueRange::default(),
+			page: 0,
+			page_size: PageSize::default(),
+			infinite: false,
+		}
+	}
+}
+
+impl From<&str> for ListQuery {
+	fn from(query: &str) -> Self {
+		let mut list_query = ListQuery::default();
+
+		for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+			let value = decode(value);
+			match key {
+				"q" => list_query.search = value,
+				"sort" => {
+					let sort = value
+						.split(',')
+						.filter_map(|key| SortKey::from_str(key).ok())
+						.collect::<Vec<_>>();
+					if !sort.is_empty() {
+						list_query.sort = sort;
+					}
+				},
+				"region" => list_query.regions = value
+					.split(',')
+					.filter_map(|region| Region::from_str(region).ok())
+					.collect(),
+				"subregion" => list_query.subregions = value
+					.split(',')
+					.filter(|subregion| !subregion.is_empty())
+					.map(str::to_string)
+					.collect(),
+				"pop" => list_query.population = ValueRange::from_str(&value).unwrap_or_default(),
+				"area" => list_query.area = ValueRange::from_str(&value).unwrap_or_default(),
+				"page" => list_query.page = value
+					.parse::<usize>()
+					.map(|page| page.saturating_sub(1))
+					.unwrap_or_default(),
+				"size" => list_query.page_size = PageSize::from_str(&value).unwrap_or_default(),
+				"scroll" => list_query.infinite = value == "1",
+				key => if let Ok(status) = Status::from_str(key) {
+					match value.as_str() {
+						"1" => list_query.statuses.insert(status, StatusFilter::Yes),
+						"0" => list_query.statuses.insert(status, StatusFilter::No),
+						_ => None,
+					};
+				}
+			}
+		}
+
+		list_query
+	}
+}
+
+/// Route enums hold the query boxed so it doesn't inflate every other route.
+impl From<&str> for Box<ListQuery> {
+	fn from(query: &str) -> Self {
+		Box::new(ListQuery::from(query))
+	}
+}
+
+impl fmt::Display for ListQuery {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let mut pairs = Vec::new();
+
+		if !self.search.is_empty() {
+			pairs.push(format!("q={}", encode(&self.search)));
+		}
+		if self.sort != ListQuery::default().sort {
+			let sort = self.sort
+				.iter()
+				.map(SortKey::to_string)
+				.collect::<Vec<_>>()
+				.join(",");
+			pairs.push(format!("sort={sort}"));
+		}
+		if !self.regions.is_empty() {
+			let regions = self.regions
+				.iter()
+				.map(Region::to_string)
+				.collect::<Vec<_>>()
+				.join(",");
+			pairs.push(format!("region={regions}"));
+		}
+		if !self.subregions.is_empty() {
+			pairs.push(format!("subregion={}", encode(&self.subregions.join(","))));
+		}
+		for status in Status::iter() {
+			match self.statuses.get(&status) {
+				Some(StatusFilter::Yes) => pairs.push(format!("{}=1", status.query_key())),
+				Some(StatusFilter::No) => pairs.push(format!("{}=0", status.query_key())),
+				Some(StatusFilter::Any) | None => {}
+			}
+		}
+		if !self.population.is_unbounded() {
+			pairs.push(format!("pop={}", self.population));
+		}
+		if !self.area.is_unbounded() {
+			pairs.push(format!("area={}", self.area));
+		}
+		if self.page > 0 {
+			pairs.push(format!("page={}", self.page + 1));
+		}
+		if self.page_size != PageSize::default() {
+			pairs.push(format!("size={}", self.page_size.to_string().to_lowercase()));
+		}
+		if self.infinite {
+			pairs.push("scroll=1".to_string());
+		}
+
+		write!(f, "{}", pairs.join("&"))
+	}
+}
+
+/// Characters that split a query string or change how it decodes.
+const DELIMITERS: &AsciiSet = &CONTROLS.add(b'%').add(b'&').add(b'=').add(b'#').add(b'+');
+/// Everything but letters, digits and the punctuation the filters use, as in a URL query component.
+const COMPONENT: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'_').remove(b'.').remove(b'~').remove(b',').remove(b':');
+
+/// Percent-encodes a value as a query component. The router percent-decodes the whole query
+/// string before it is split into pairs, so [`DELIMITERS`] are escaped twice to survive that pass.
+fn encode(value: &str) -> String {
+	let delimiters_escaped = utf8_percent_encode(value, DELIMITERS).to_string();
+	utf8_percent_encode(&delimiters_escaped, COMPONENT).to_string()
+}
+
+fn decode(value: &str) -> String {
+	percent_decode_str(value).decode_utf8_lossy().into_owned()
+}
@@ -0,0 +1,13 @@
+mod cca3;
+mod countries_api;
+mod filters;
+mod list_query;
+mod paginator;
+mod route_query;
+
+pub use cca3::*;
+pub use countries_api::*;
+pub use filters::*;
+pub use list_query::*;
+pub use paginator::*;
+pub use route_query::*;
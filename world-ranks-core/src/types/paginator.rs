@@ -0,0 +1,141 @@
+use std::ops::Range;
+
+/// Splits `len` items into pages and keeps the current page in range as the length changes.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Paginator {
+	/// The page asked for, which may be past the end until it is read through `page()`.
+	requested: usize,
+	/// `None` puts every item on one page.
+	page_size: Option<usize>,
+	len: usize,
+}
+
+impl Paginator {
+	pub fn new(page: usize, page_size: Option<usize>, len: usize) -> Self {
+		Paginator {
+			requested: page,
+			page_size: page_size.filter(|size| *size > 0),
+			len,
+		}
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Zero when there are no items.
+	pub fn total_pages(&self) -> usize {
+		match self.page_size {
+			Some(size) => self.len.div_ceil(size),
+			None => usize::from(self.len > 0),
+		}
+	}
+
+	pub fn last_page(&self) -> usize {
+		self.total_pages().saturating_sub(1)
+	}
+
+	/// The current page, clamped to the last page. Without any items the requested page is
+	/// kept, so a page from the URL survives until the data has loaded.
+	pub fn page(&self) -> usize {
+		if self.is_empty() {
+			self.requested
+		} else {
+			self.requested.min(self.last_page())
+		}
+	}
+
+	pub fn has_previous(&self) -> bool {
+		self.page() > 0
+	}
+
+	pub fn has_next(&self) -> bool {
+		self.page() < self.last_page()
+	}
+
+	/// Indices of the items on the current page.
+	pub fn range(&self) -> Range<usize> {
+		let Some(size) = self.page_size else {
+			return 0..self.len;
+		};
+		let start = (self.page() * size).min(self.len);
+		start..(start + size).min(self.len)
+	}
+
+	/// Indices of the items on every page up to and including the current one, as shown by
+	/// an infinite list.
+	pub fn range_through(&self) -> Range<usize> {
+		0..self.range().end
+	}
+
+	/// Page numbers to show in a pager, with `None` marking a gap. The first and last page
+	/// are always shown, along with the pages either side of the current one.
+	pub fn visible_pages(&self) -> Vec<Option<usize>> {
+		let current = self.page();
+		let total = self.total_pages();
+		let mut pages = Vec::new();
+		for page in 0..total {
+			if page == 0 || page + 1 == total || page.abs_diff(current) <= 1 {
+				pages.push(Some(page));
+			} else if pages.last() != Some(&None) {
+				pages.push(None);
+			}
+		}
+		pages
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn total_pages_rounds_up() {
+		assert_eq!(Paginator::new(0, Some(15), 0).total_pages(), 0);
+		assert_eq!(Paginator::new(0, Some(15), 15).total_pages(), 1);
+		assert_eq!(Paginator::new(0, Some(15), 16).total_pages(), 2);
+		assert_eq!(Paginator::new(0, None, 250).total_pages(), 1);
+	}
+
+	#[test]
+	fn page_is_clamped_to_the_last_page() {
+		let paginator = Paginator::new(9, Some(15), 40);
+		assert_eq!(paginator.page(), 2);
+		assert_eq!(paginator.range(), 30..40);
+		assert!(!paginator.has_next());
+		assert!(paginator.has_previous());
+	}
+
+	#[test]
+	fn requested_page_is_kept_without_items() {
+		let paginator = Paginator::new(3, Some(15), 0);
+		assert_eq!(paginator.page(), 3);
+		assert_eq!(paginator.range(), 0..0);
+	}
+
+	#[test]
+	fn ranges_cover_the_current_page() {
+		let paginator = Paginator::new(1, Some(15), 40);
+		assert_eq!(paginator.range(), 15..30);
+		assert_eq!(paginator.range_through(), 0..30);
+		assert_eq!(Paginator::new(2, None, 40).range(), 0..40);
+	}
+
+	#[test]
+	fn zero_page_size_shows_everything() {
+		assert_eq!(Paginator::new(0, Some(0), 7).range(), 0..7);
+	}
+
+	#[test]
+	fn visible_pages_collapse_with_gaps() {
+		assert_eq!(Paginator::new(0, Some(1), 1).visible_pages(), vec![Some(0)]);
+		assert_eq!(
+			Paginator::new(8, Some(1), 17).visible_pages(),
+			vec![Some(0), None, Some(7), Some(8), Some(9), None, Some(16)]
+		);
+		assert_eq!(
+			Paginator::new(0, Some(1), 5).visible_pages(),
+			vec![Some(0), Some(1), None, Some(4)]
+		);
+	}
+}
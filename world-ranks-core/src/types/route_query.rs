@@ -0,0 +1,37 @@
+use std::fmt;
+use std::str::FromStr;
+use crate::types::CCA3;
+
+/// The two countries picked on the land route page, stored in the URL as `?from=PRT&to=CHN`.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct RouteQuery {
+	pub from: Option<CCA3>,
+	pub to: Option<CCA3>,
+}
+
+impl From<&str> for RouteQuery {
+	fn from(query: &str) -> Self {
+		let mut route_query = RouteQuery::default();
+
+		for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+			match key {
+				"from" => route_query.from = CCA3::from_str(value).ok(),
+				"to" => route_query.to = CCA3::from_str(value).ok(),
+				_ => {}
+			}
+		}
+
+		route_query
+	}
+}
+
+impl fmt::Display for RouteQuery {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let pairs = [("from", self.from), ("to", self.to)]
+			.into_iter()
+			.filter_map(|(key, code)| Some(format!("{key}={}", code?)))
+			.collect::<Vec<_>>();
+
+		write!(f, "{}", pairs.join("&"))
+	}
+}
@@ -0,0 +1,32 @@
+use std::str::FromStr;
+use world_ranks_core::types::{CountryOverview, Flags, Name, Region, CCA3};
+
+pub fn country(cca3: &str, name: &str, region: Region, sub_region: &str, population: u32, area: f32) -> CountryOverview {
+	CountryOverview {
+		name: Name {
+			common: name.to_string(),
+			official: name.to_string(),
+			native_name: Default::default()
+		},
+		cca3: CCA3::from_str(cca3).unwrap(),
+		cca2: cca3[..2].to_string(),
+		alt_spellings: Vec::new(),
+		independent: true,
+		un_member: true,
+		region,
+		sub_region: sub_region.to_string(),
+		area,
+		population,
+		flags: Flags {
+			png: String::new(),
+			svg: String::new(),
+			alt: None
+		},
+		borders: None
+	}
+}
+
+#[allow(dead_code)]
+pub fn codes(countries: &[CountryOverview]) -> Vec<&str> {
+	countries.iter().map(|c| c.cca3.as_str()).collect()
+}
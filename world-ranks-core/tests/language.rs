@@ -0,0 +1,115 @@
+mod common;
+
+use world_ranks_core::{
+	filter_data,
+	search::{ParsedQuery, SearchQuery},
+	types::{ListQuery, Region, SortBy, SortDirection, SortKey, Status, StatusFilter, ValueRange},
+};
+use common::{codes, country};
+
+#[test]
+fn separates_filters_from_text() {
+	let parsed = ParsedQuery::parse("region:europe,asia un:yes \"united kingdom\" sub:\"Western Europe\"");
+
+	assert!(parsed.is_valid(), "{:?}", parsed.errors);
+	assert_eq!(parsed.regions, [Region::Europe, Region::Asia]);
+	assert_eq!(parsed.subregions, ["Western Europe"]);
+	assert_eq!(parsed.statuses.get(&Status::UN), Some(&StatusFilter::Yes));
+	assert!(parsed.text == SearchQuery::new("united kingdom"));
+}
+
+#[test]
+fn reads_comparisons_and_unit_suffixes() {
+	let parsed = ParsedQuery::parse("pop>=10m pop<1.5b area:2,500-50k");
+
+	assert!(parsed.is_valid(), "{:?}", parsed.errors);
+	assert_eq!(parsed.population, ValueRange {
+		min: Some(10_000_000.0),
+		max: Some(1_500_000_000.0_f64.next_down())
+	});
+	assert_eq!(parsed.area, ValueRange {
+		min: Some(2_500.0),
+		max: Some(50_000.0)
+	});
+
+	let parsed = ParsedQuery::parse("area=1k");
+	assert_eq!(parsed.area, ValueRange { min: Some(1_000.0), max: Some(1_000.0) });
+}
+
+#[test]
+fn strict_comparisons_exclude_their_bound() {
+	let countries = [
+		country("AAA", "Small", Region::Europe, "", 10_000_000, 1.0),
+		country("BBB", "Large", Region::Europe, "", 10_000_001, 1.0),
+	];
+	let parsed = ParsedQuery::parse("pop>10m");
+
+	assert_eq!(codes(&filter_data(&countries, &parsed.filters())), ["BBB"]);
+}
+
+#[test]
+fn reads_sort_keys_with_directions() {
+	let parsed = ParsedQuery::parse("sort:-density,name");
+
+	assert!(parsed.is_valid(), "{:?}", parsed.errors);
+	assert_eq!(parsed.sort, [
+		SortKey { by: SortBy::Density, direction: SortDirection::Descending },
+		SortKey { by: SortBy::Name, direction: SortDirection::Ascending },
+	]);
+
+	let chosen = [SortKey::new(SortBy::Area)];
+	assert_eq!(parsed.sort_or(&chosen), parsed.sort);
+	assert_eq!(ParsedQuery::parse("japan").sort_or(&chosen), chosen);
+}
+
+#[test]
+fn reports_bad_tokens_where_they_are() {
+	let input = "japan colour:red pop>lots region:atlantis un:maybe \"open";
+	let parsed = ParsedQuery::parse(input);
+
+	let tokens = parsed.errors.iter().map(|e| &input[e.span.clone()]).collect::<Vec<_>>();
+	assert_eq!(tokens, ["colour:red", "pop>lots", "region:atlantis", "un:maybe", "\"open"]);
+	assert!(parsed.errors[0].message.starts_with("Unknown filter \"colour\""));
+	assert!(parsed.errors[1].message.contains("\"lots\""));
+	assert_eq!(parsed.errors[4].to_string(), "\"open: Missing the closing quote");
+	// Bad tokens are left out of the text instead of matching nothing
+	assert!(parsed.text == SearchQuery::new("japan"));
+}
+
+#[test]
+fn rejects_missing_values_and_misplaced_operators() {
+	let parsed = ParsedQuery::parse("region: un>yes sort=name pop:10m");
+
+	let messages = parsed.errors.iter().map(|e| e.message.as_str()).collect::<Vec<_>>();
+	assert_eq!(messages, [
+		"Missing a value after region",
+		"Use un:yes",
+		"Use sort:name",
+		"Expected a range such as 1m-10m, 1m- or -10m",
+	]);
+}
+
+#[test]
+fn text_that_only_looks_like_a_filter_stays_text() {
+	let parsed = ParsedQuery::parse("\"a:b\" 10:30 -5");
+
+	assert!(parsed.is_valid(), "{:?}", parsed.errors);
+	assert!(parsed.text == SearchQuery::new("a:b 10:30 -5"));
+}
+
+#[test]
+fn list_queries_combine_the_search_with_their_filters() {
+	let countries = [
+		country("DEU", "Germany", Region::Europe, "Western Europe", 83_240_525, 357_114.0),
+		country("FRA", "France", Region::Europe, "Western Europe", 67_391_582, 551_695.0),
+		country("MCO", "Monaco", Region::Europe, "Western Europe", 39_244, 2.02),
+		country("JPN", "Japan", Region::Asia, "Eastern Asia", 125_836_021, 377_930.0),
+	];
+	let query = ListQuery {
+		search: "pop>1m sort:-area".to_string(),
+		regions: vec![Region::Europe],
+		..ListQuery::default()
+	};
+
+	assert_eq!(codes(&query.apply(&countries)), ["FRA", "DEU"]);
+}
@@ -0,0 +1,209 @@
+//! Properties checked over randomly generated countries and queries. Each test runs a fixed
+//! number of cases from a fixed seed, so a failure always reproduces.
+
+mod common;
+
+use std::{cmp::Ordering, collections::HashMap};
+use percent_encoding::percent_decode_str;
+use rand::{rngs::StdRng, seq::IndexedRandom, Rng, SeedableRng};
+use strum::IntoEnumIterator;
+use world_ranks_core::{
+	filter_data,
+	search::SearchQuery,
+	sort_data,
+	toggle_region,
+	types::{
+		CountryOverview,
+		FilterQuery,
+		ListQuery,
+		PageSize,
+		Region,
+		SortBy,
+		SortDirection,
+		SortKey,
+		Status,
+		StatusFilter,
+		ValueRange
+	},
+};
+use common::country;
+
+const CASES: u64 = 256;
+const NAMES: [&str; 8] = ["Germany", "Japan", "Côte d'Ivoire", "Åland Islands", "Chad", "Peru", "Niger", "Nigeria"];
+const SUBREGIONS: [&str; 4] = ["Western Europe", "Eastern Asia", "South America", ""];
+
+fn cases(test: impl Fn(&mut StdRng)) {
+	for seed in 0..CASES {
+		test(&mut StdRng::seed_from_u64(seed));
+	}
+}
+
+fn random_region(rng: &mut StdRng) -> Region {
+	*Region::iter().collect::<Vec<_>>().choose(rng).unwrap()
+}
+
+fn random_range(rng: &mut StdRng, max: f64) -> ValueRange {
+	let mut bound = || rng.random_bool(0.5).then(|| rng.random_range(0..max as u64) as f64);
+	ValueRange {
+		min: bound(),
+		max: bound()
+	}
+}
+
+fn random_countries(rng: &mut StdRng) -> Vec<CountryOverview> {
+	(0..rng.random_range(0..40))
+		.map(|_| {
+			let code = (0..3).map(|_| rng.random_range(b'A'..=b'Z') as char).collect::<String>();
+			let mut country = country(
+				&code,
+				NAMES.choose(rng).unwrap(),
+				random_region(rng),
+				SUBREGIONS.choose(rng).unwrap(),
+				rng.random_range(0..2_000_000),
+				// Zero areas give NaN densities
+				rng.random_range(0..20_000) as f32
+			);
+			country.independent = rng.random_bool(0.5);
+			country.un_member = rng.random_bool(0.5);
+			country
+		})
+		.collect()
+}
+
+fn random_sort(rng: &mut StdRng) -> Vec<SortKey> {
+	let keys = SortBy::iter().collect::<Vec<_>>();
+	(0..rng.random_range(1..4))
+		.map(|_| SortKey {
+			by: *keys.choose(rng).unwrap(),
+			direction: *[SortDirection::Ascending, SortDirection::Descending].choose(rng).unwrap()
+		})
+		.collect()
+}
+
+fn random_statuses(rng: &mut StdRng) -> HashMap<Status, StatusFilter> {
+	Status::iter()
+		.filter_map(|status| {
+			let filter = *[StatusFilter::Any, StatusFilter::Yes, StatusFilter::No].choose(rng).unwrap();
+			rng.random_bool(0.5).then_some((status, filter))
+		})
+		.collect()
+}
+
+#[test]
+fn filtering_keeps_exactly_the_countries_matching_every_query() {
+	cases(|rng| {
+		let countries = random_countries(rng);
+		let search = SearchQuery::new(if rng.random_bool(0.3) { NAMES.choose(rng).unwrap() } else { "" });
+		let regions = (0..rng.random_range(0..3)).map(|_| random_region(rng)).collect::<Vec<_>>();
+		let statuses = random_statuses(rng);
+		let queries = [
+			FilterQuery::Text(&search),
+			FilterQuery::Region(&regions),
+			FilterQuery::Status(&statuses),
+			FilterQuery::PopulationRange(random_range(rng, 2_000_000.0)),
+			FilterQuery::AreaRange(random_range(rng, 20_000.0)),
+		];
+
+		let filtered = filter_data(&countries, &queries);
+		let expected = countries
+			.iter()
+			.filter(|&country| queries.iter().all(|query| filter_data(std::slice::from_ref(country), &[*query]).len() == 1))
+			.cloned()
+			.collect::<Vec<_>>();
+
+		assert!(filtered == expected, "filtering is not the intersection of every query");
+	});
+}
+
+#[test]
+fn sorting_orders_a_permutation_of_the_input() {
+	cases(|rng| {
+		let countries = random_countries(rng);
+		let sort = random_sort(rng);
+		let sorted = sort_data(countries.clone(), &sort);
+
+		let mut before = countries.iter().map(|c| c.cca3).collect::<Vec<_>>();
+		let mut after = sorted.iter().map(|c| c.cca3).collect::<Vec<_>>();
+		before.sort();
+		after.sort();
+		assert_eq!(before, after);
+
+		let compare = |a: &CountryOverview, b: &CountryOverview| {
+			sort.iter().fold(Ordering::Equal, |ordering, key| ordering.then_with(|| key.compare(a, b)))
+		};
+		for pair in sorted.windows(2) {
+			assert_ne!(compare(&pair[0], &pair[1]), Ordering::Greater, "sorted by {sort:?}");
+		}
+	});
+}
+
+#[test]
+fn sorting_twice_changes_nothing() {
+	cases(|rng| {
+		let sort = random_sort(rng);
+		let once = sort_data(random_countries(rng), &sort);
+		let twice = sort_data(once.clone(), &sort);
+
+		assert!(once == twice, "sorting by {sort:?} again reordered countries");
+	});
+}
+
+#[test]
+fn toggling_a_region_twice_restores_the_filter() {
+	cases(|rng| {
+		let mut regions = Region::iter().filter(|_| rng.random_bool(0.5)).collect::<Vec<_>>();
+		let original = regions.clone();
+		let region = random_region(rng);
+
+		toggle_region(&mut regions, region);
+		assert_ne!(regions.contains(&region), original.contains(&region));
+		toggle_region(&mut regions, region);
+
+		let mut restored = regions.clone();
+		let mut original = original;
+		restored.sort_by_key(Region::to_string);
+		original.sort_by_key(Region::to_string);
+		assert_eq!(restored, original);
+	});
+}
+
+#[test]
+fn list_queries_survive_the_url() {
+	cases(|rng| {
+		let search = (0..rng.random_range(0..3)).map(|_| *NAMES.choose(rng).unwrap()).collect::<Vec<_>>();
+		let query = ListQuery {
+			search: format!("{} &=%", search.join(" ")).trim().to_string(),
+			sort: random_sort(rng),
+			regions: Region::iter().filter(|_| rng.random_bool(0.3)).collect(),
+			subregions: SUBREGIONS.iter().filter(|s| !s.is_empty() && rng.random_bool(0.3)).map(|s| s.to_string()).collect(),
+			statuses: random_statuses(rng)
+				.into_iter()
+				.filter(|(_, filter)| *filter != StatusFilter::Any)
+				.collect(),
+			population: random_range(rng, 2_000_000.0),
+			area: random_range(rng, 20_000.0),
+			page: rng.random_range(0..10),
+			page_size: *PageSize::iter().collect::<Vec<_>>().choose(rng).unwrap(),
+			infinite: rng.random_bool(0.5),
+		};
+
+		// The router percent-decodes the query string once before parsing it
+		let url = percent_decode_str(&query.to_string()).decode_utf8_lossy().into_owned();
+		assert_eq!(ListQuery::from(url.as_str()), query, "through {url}");
+	});
+}
+
+#[test]
+fn ranges_include_their_bounds_and_survive_the_url() {
+	cases(|rng| {
+		let range = random_range(rng, 1_000.0);
+
+		for bound in [range.min, range.max].into_iter().flatten() {
+			assert_eq!(range.contains(bound), range.min <= range.max || range.max.is_none() || range.min.is_none());
+		}
+		if range.is_unbounded() {
+			assert!(range.contains(rng.random_range(0..1_000) as f64));
+		}
+		assert_eq!(range.to_string().parse::<ValueRange>().unwrap(), range);
+	});
+}
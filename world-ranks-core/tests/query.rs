@@ -0,0 +1,213 @@
+mod common;
+
+use std::collections::HashMap;
+use percent_encoding::percent_decode_str;
+use world_ranks_core::{
+	filter_data,
+	search::SearchQuery,
+	sort_data,
+	toggle_region,
+	toggle_subregion,
+	types::{CountryOverview, FilterQuery, ListQuery, Region, SortBy, SortDirection, SortKey, Status, StatusFilter, ValueRange},
+};
+use common::{codes, country};
+
+fn countries() -> Vec<CountryOverview> {
+	let mut monaco = country("MCO", "Monaco", Region::Europe, "Western Europe", 39_244, 2.02);
+	monaco.un_member = true;
+	let mut kosovo = country("UNK", "Kosovo", Region::Europe, "Southeast Europe", 1_775_378, 10_908.0);
+	kosovo.un_member = false;
+	let mut greenland = country("GRL", "Greenland", Region::Americas, "North America", 56_367, 2_166_086.0);
+	greenland.independent = false;
+	greenland.un_member = false;
+
+	vec![
+		country("DEU", "Germany", Region::Europe, "Western Europe", 83_240_525, 357_114.0),
+		country("JPN", "Japan", Region::Asia, "Eastern Asia", 125_836_021, 377_930.0),
+		monaco,
+		kosovo,
+		greenland,
+		country("FRA", "France", Region::Europe, "Western Europe", 67_391_582, 551_695.0),
+		country("ATA", "Antarctica", Region::Antarctic, "", 1_000, 14_000_000.0),
+	]
+}
+
+#[test]
+fn empty_filters_match_every_country() {
+	let search = SearchQuery::new("");
+	let filtered = filter_data(&countries(), &[
+		FilterQuery::Text(&search),
+		FilterQuery::Region(&[]),
+		FilterQuery::Subregion(&[]),
+		FilterQuery::Status(&HashMap::new()),
+		FilterQuery::PopulationRange(ValueRange::default()),
+		FilterQuery::AreaRange(ValueRange::default()),
+	]);
+
+	assert_eq!(codes(&filtered), codes(&countries()));
+}
+
+#[test]
+fn filters_by_region_and_subregion() {
+	let regions = [Region::Europe, Region::Asia];
+	let subregions = ["Western Europe".to_string(), "Eastern Asia".to_string()];
+
+	let filtered = filter_data(&countries(), &[FilterQuery::Region(&regions)]);
+	assert_eq!(codes(&filtered), ["DEU", "JPN", "MCO", "UNK", "FRA"]);
+
+	let filtered = filter_data(&countries(), &[FilterQuery::Region(&regions[..1]), FilterQuery::Subregion(&subregions)]);
+	assert_eq!(codes(&filtered), ["DEU", "MCO", "FRA"]);
+}
+
+#[test]
+fn filters_by_status() {
+	let not_un = HashMap::from([(Status::UN, StatusFilter::No)]);
+	assert_eq!(codes(&filter_data(&countries(), &[FilterQuery::Status(&not_un)])), ["UNK", "GRL"]);
+
+	let dependent_outside_un = HashMap::from([(Status::UN, StatusFilter::No), (Status::Independent, StatusFilter::No)]);
+	assert_eq!(codes(&filter_data(&countries(), &[FilterQuery::Status(&dependent_outside_un)])), ["GRL"]);
+
+	let any = HashMap::from([(Status::UN, StatusFilter::Any)]);
+	assert_eq!(filter_data(&countries(), &[FilterQuery::Status(&any)]).len(), countries().len());
+}
+
+#[test]
+fn filters_by_every_combination_of_statuses() {
+	let countries = [(true, true), (true, false), (false, true), (false, false)]
+		.into_iter()
+		.zip(["AAA", "BBB", "CCC", "DDD"])
+		.map(|((independent, un_member), code)| {
+			let mut country = country(code, code, Region::Europe, "", 1, 1.0);
+			country.independent = independent;
+			country.un_member = un_member;
+			country
+		})
+		.collect::<Vec<_>>();
+
+	use StatusFilter::{Any, No, Yes};
+	// UN filter, independence filter, and the countries they keep
+	let expected: [(StatusFilter, StatusFilter, &[&str]); 9] = [
+		(Any, Any, &["AAA", "BBB", "CCC", "DDD"]),
+		(Any, Yes, &["AAA", "BBB"]),
+		(Any, No, &["CCC", "DDD"]),
+		(Yes, Any, &["AAA", "CCC"]),
+		(Yes, Yes, &["AAA"]),
+		(Yes, No, &["CCC"]),
+		(No, Any, &["BBB", "DDD"]),
+		(No, Yes, &["BBB"]),
+		(No, No, &["DDD"]),
+	];
+
+	for (un, independent, codes_kept) in expected {
+		let statuses = HashMap::from([(Status::UN, un), (Status::Independent, independent)]);
+		let filtered = filter_data(&countries, &[FilterQuery::Status(&statuses)]);
+		assert_eq!(codes(&filtered), codes_kept, "UN {un:?}, independent {independent:?}");
+	}
+}
+
+#[test]
+fn filters_by_inclusive_ranges() {
+	let population = ValueRange {
+		min: Some(39_244.0),
+		max: Some(1_775_378.0)
+	};
+	assert_eq!(codes(&filter_data(&countries(), &[FilterQuery::PopulationRange(population)])), ["MCO", "UNK", "GRL"]);
+
+	let area = ValueRange {
+		min: None,
+		max: Some(357_114.0)
+	};
+	assert_eq!(codes(&filter_data(&countries(), &[FilterQuery::AreaRange(area)])), ["DEU", "MCO", "UNK"]);
+}
+
+#[test]
+fn filters_by_search_text() {
+	let search = SearchQuery::new("germny");
+	assert_eq!(codes(&filter_data(&countries(), &[FilterQuery::Text(&search)])), ["DEU"]);
+
+	let search = SearchQuery::new("jpn");
+	assert_eq!(codes(&filter_data(&countries(), &[FilterQuery::Text(&search)])), ["JPN"]);
+}
+
+#[test]
+fn sorts_on_several_keys() {
+	let sort = [
+		SortKey::new(SortBy::Region),
+		SortKey::new(SortBy::Population),
+	];
+	let sorted = sort_data(countries(), &sort);
+
+	assert_eq!(codes(&sorted), ["GRL", "ATA", "JPN", "DEU", "FRA", "UNK", "MCO"]);
+}
+
+#[test]
+fn sort_keeps_the_order_of_ties() {
+	let sort = [SortKey {
+		by: SortBy::Subregion,
+		direction: SortDirection::Ascending
+	}];
+	let sorted = sort_data(countries(), &sort);
+
+	assert_eq!(codes(&sorted), ["ATA", "JPN", "GRL", "UNK", "DEU", "MCO", "FRA"]);
+}
+
+#[test]
+fn sorts_missing_densities_last_in_either_direction() {
+	let mut countries = countries();
+	countries[0].area = 0.0;
+
+	let descending = sort_data(countries.clone(), &[SortKey::new(SortBy::Density)]);
+	assert_eq!(descending[0].cca3.as_str(), "MCO");
+	assert_eq!(descending.last().unwrap().cca3.as_str(), "DEU");
+
+	let ascending = sort_data(countries, &[SortKey {
+		by: SortBy::Density,
+		direction: SortDirection::Ascending
+	}]);
+	assert_eq!(ascending[0].cca3.as_str(), "ATA");
+	assert_eq!(ascending.last().unwrap().cca3.as_str(), "DEU");
+}
+
+#[test]
+fn toggles_filters_on_and_off() {
+	let mut regions = vec![Region::Asia];
+	toggle_region(&mut regions, Region::Europe);
+	assert_eq!(regions, [Region::Asia, Region::Europe]);
+	toggle_region(&mut regions, Region::Asia);
+	assert_eq!(regions, [Region::Europe]);
+
+	let mut subregions = Vec::new();
+	toggle_subregion(&mut subregions, "Western Europe");
+	assert_eq!(subregions, ["Western Europe"]);
+	toggle_subregion(&mut subregions, "Western Europe");
+	assert!(subregions.is_empty());
+
+	// Typed into a URL in another case
+	let mut subregions = vec!["western europe".to_string()];
+	toggle_subregion(&mut subregions, "Western Europe");
+	assert!(subregions.is_empty());
+}
+
+#[test]
+fn applies_a_list_query_from_the_url() {
+	let query = ListQuery::from("region=Europe&un=1&sort=Name&pop=1000000-");
+	assert_eq!(codes(&query.apply(&countries())), ["FRA", "DEU"]);
+
+	let query = ListQuery::from("q=western&sort=-Area");
+	assert_eq!(codes(&query.apply(&countries())), ["FRA", "DEU", "MCO"]);
+}
+
+#[test]
+fn list_queries_escape_url_syntax() {
+	let query = ListQuery {
+		search: "#1 a+b why? 100% & = Côte d'Ivoire".to_string(),
+		subregions: vec!["Micronésie#+?".to_string()],
+		..ListQuery::default()
+	};
+	let url = query.to_string();
+	assert!(!url.contains(['#', '+', '?', ' ', 'ô', 'é']), "{url}");
+
+	// The router percent-decodes the query string once before parsing it
+	let decoded = percent_decode_str(&url).decode_utf8_lossy().into_owned();
+	assert_eq!(ListQuery::from(decoded.as_str()), query, "through {decoded}");
+}
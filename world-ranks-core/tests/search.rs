@@ -0,0 +1,84 @@
+mod common;
+
+use world_ranks_core::{
+	search::{normalize, SearchQuery},
+	types::{CountryOverview, Region},
+};
+use common::{codes, country};
+
+fn countries() -> Vec<CountryOverview> {
+	let mut germany = country("DEU", "Germany", Region::Europe, "Western Europe", 83_240_525, 357_114.0);
+	germany.name.official = "Federal Republic of Germany".to_string();
+	germany.alt_spellings = vec!["Deutschland".to_string()];
+
+	vec![
+		country("NGA", "Nigeria", Region::Africa, "Western Africa", 206_139_587, 923_768.0),
+		country("NER", "Niger", Region::Africa, "Western Africa", 24_206_636, 1_267_000.0),
+		germany,
+		country("CIV", "Côte d'Ivoire", Region::Africa, "Western Africa", 26_378_275, 322_463.0),
+		country("PER", "Peru", Region::Americas, "South America", 32_971_846, 1_285_216.0),
+	]
+}
+
+#[test]
+fn normalizes_case_diacritics_and_punctuation() {
+	assert_eq!(normalize("Côte d'Ivoire"), "cote d ivoire");
+	assert_eq!(normalize("Åland Islands"), "aland islands");
+	assert_eq!(normalize("  São Tomé & Príncipe "), "sao tome principe");
+	assert_eq!(normalize("Straße, Færøerne"), "strasse faeroerne");
+	assert_eq!(normalize("ŁÓDŹ"), "lodz");
+}
+
+#[test]
+fn an_empty_query_keeps_every_country_in_order() {
+	let search = SearchQuery::new("  ");
+
+	assert!(search.is_empty());
+	assert_eq!(codes(&search.rank(countries())), codes(&countries()));
+}
+
+#[test]
+fn codes_match_exactly() {
+	let search = SearchQuery::new("DEU");
+	assert_eq!(search.score(&countries()[2]), Some(1.0));
+
+	assert_eq!(codes(&SearchQuery::new("ne").rank(countries())), ["NER"]);
+}
+
+#[test]
+fn ranks_exact_names_above_prefixes_words_and_substrings() {
+	assert_eq!(codes(&SearchQuery::new("niger").rank(countries())), ["NER", "NGA"]);
+	// "republic" is a word inside the official name, "many" only a substring of the common one
+	assert_eq!(codes(&SearchQuery::new("republic").rank(countries())), ["DEU"]);
+	assert_eq!(codes(&SearchQuery::new("many").rank(countries())), ["DEU"]);
+	assert_eq!(codes(&SearchQuery::new("deutsch").rank(countries())), ["DEU"]);
+}
+
+#[test]
+fn matches_regions_and_subregions_below_names() {
+	// Only a substring in the middle of "Western Africa" and "Western Europe"
+	assert_eq!(codes(&SearchQuery::new("frica").rank(countries())), ["NGA", "NER", "CIV"]);
+	assert_eq!(codes(&SearchQuery::new("rope").rank(countries())), ["DEU"]);
+
+	let search = SearchQuery::new("peru");
+	let place = SearchQuery::new("america").score(&countries()[4]).unwrap();
+	assert!(search.score(&countries()[4]).unwrap() > place);
+}
+
+#[test]
+fn tolerates_typos_and_accents() {
+	assert_eq!(codes(&SearchQuery::new("germny").rank(countries())), ["DEU"]);
+	assert_eq!(codes(&SearchQuery::new("cote divoire").rank(countries())), ["CIV"]);
+	assert_eq!(codes(&SearchQuery::new("Côte").rank(countries())), ["CIV"]);
+	assert!(SearchQuery::new("xyzzy").rank(countries()).is_empty());
+}
+
+#[test]
+fn keeps_the_order_of_equally_relevant_results() {
+	let search = SearchQuery::new("western africa");
+	let mut reversed = countries();
+	reversed.reverse();
+
+	assert_eq!(codes(&search.rank(countries())), ["NGA", "NER", "CIV"]);
+	assert_eq!(codes(&search.rank(reversed)), ["CIV", "NER", "NGA"]);
+}